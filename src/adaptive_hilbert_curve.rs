@@ -0,0 +1,201 @@
+use image::{ImageBuffer, Luma};
+use num::{traits::Euclid, Float};
+use std::marker::PhantomData;
+
+use crate::{base_curve::BaseCurve, line_utils::connect_points};
+
+/// Hilbert curve whose order varies over the image, see [crate::hilbert_curve::HilbertCurve]
+///
+/// The image is subdivided like a quadtree in Hilbert order, so the cells have the aspect ratio of the image.
+/// A cell is subdivided further only while the standard deviation of its brightness exceeds a threshold,
+/// so flat regions get few turns and detailed regions many. The centers of the final cells are connected in order,
+/// which keeps the path continuous across cells of different size and within the image.
+#[derive(Clone, Copy, Debug)]
+pub struct AdaptiveHilbertCurve<'a, T> {
+    image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+    min_order: usize,
+    max_order: usize,
+    threshold: f64,
+    _marker: PhantomData<T>,
+}
+
+/// Summed-area tables of brightness and squared brightness for constant time cell statistics
+struct Statistics {
+    width: usize,
+    height: usize,
+    sum: Vec<f64>,
+    sum_squared: Vec<f64>,
+}
+
+impl Statistics {
+    fn new(image: &ImageBuffer<Luma<u8>, Vec<u8>>) -> Statistics {
+        let width = image.width() as usize;
+        let height = image.height() as usize;
+        let mut sum = vec![0f64; (width + 1) * (height + 1)];
+        let mut sum_squared = vec![0f64; (width + 1) * (height + 1)];
+        for y in 0..height {
+            for x in 0..width {
+                let value = image.get_pixel(x as u32, y as u32).0[0] as f64;
+                let index = (y + 1) * (width + 1) + x + 1;
+                let above = y * (width + 1) + x + 1;
+                sum[index] = value + sum[index - 1] + sum[above] - sum[above - 1];
+                sum_squared[index] = value * value + sum_squared[index - 1] + sum_squared[above]
+                    - sum_squared[above - 1];
+            }
+        }
+        Statistics {
+            width,
+            height,
+            sum,
+            sum_squared,
+        }
+    }
+
+    /// Returns the standard deviation of the brightness of the pixels within the given bounds
+    fn standard_deviation(&self, min: (f64, f64), max: (f64, f64)) -> f64 {
+        let clamp_x = |x: f64| (x.round().max(0.0) as usize).min(self.width);
+        let clamp_y = |y: f64| (y.round().max(0.0) as usize).min(self.height);
+        let (x0, x1) = (clamp_x(min.0), clamp_x(max.0));
+        let (y0, y1) = (clamp_y(min.1), clamp_y(max.1));
+        if x1 <= x0 || y1 <= y0 {
+            return 0.0;
+        }
+        let area = |table: &[f64]| {
+            table[y1 * (self.width + 1) + x1]
+                - table[y0 * (self.width + 1) + x1]
+                - table[y1 * (self.width + 1) + x0]
+                + table[y0 * (self.width + 1) + x0]
+        };
+        let num = ((x1 - x0) * (y1 - y0)) as f64;
+        let mean = area(&self.sum) / num;
+        (area(&self.sum_squared) / num - mean * mean)
+            .max(0.0)
+            .sqrt()
+    }
+}
+
+impl<'a, T> AdaptiveHilbertCurve<'a, T>
+where
+    T: Float,
+{
+    /// Returns a curve that is at least of `min_order` and at most of `max_order`,
+    /// cells are subdivided while the standard deviation of their brightness (0-255) exceeds `threshold`
+    pub fn new(
+        image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+        min_order: usize,
+        max_order: usize,
+        threshold: f64,
+    ) -> AdaptiveHilbertCurve<'a, T> {
+        AdaptiveHilbertCurve {
+            image,
+            min_order: min_order.min(max_order),
+            max_order,
+            threshold,
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Appends the centers of the final cells of the rectangle at `origin` spanned by the axes `i` and `j`
+    fn visit_cell(
+        &self,
+        statistics: &Statistics,
+        points: &mut Vec<(f64, f64)>,
+        origin: (f64, f64),
+        i: (f64, f64),
+        j: (f64, f64),
+        order: usize,
+    ) {
+        let corners = [
+            origin,
+            (origin.0 + i.0, origin.1 + i.1),
+            (origin.0 + j.0, origin.1 + j.1),
+            (origin.0 + i.0 + j.0, origin.1 + i.1 + j.1),
+        ];
+        let min = corners
+            .iter()
+            .fold((f64::INFINITY, f64::INFINITY), |min, corner| {
+                (min.0.min(corner.0), min.1.min(corner.1))
+            });
+        let max = corners
+            .iter()
+            .fold((f64::NEG_INFINITY, f64::NEG_INFINITY), |max, corner| {
+                (max.0.max(corner.0), max.1.max(corner.1))
+            });
+        let subdivide = order < self.max_order
+            && (order < self.min_order || statistics.standard_deviation(min, max) > self.threshold);
+        if !subdivide {
+            points.push(((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0));
+            return;
+        }
+        let half_i = (i.0 / 2.0, i.1 / 2.0);
+        let half_j = (j.0 / 2.0, j.1 / 2.0);
+        self.visit_cell(statistics, points, origin, half_j, half_i, order + 1);
+        self.visit_cell(
+            statistics,
+            points,
+            (origin.0 + half_i.0, origin.1 + half_i.1),
+            half_i,
+            half_j,
+            order + 1,
+        );
+        self.visit_cell(
+            statistics,
+            points,
+            (
+                origin.0 + half_i.0 + half_j.0,
+                origin.1 + half_i.1 + half_j.1,
+            ),
+            half_i,
+            half_j,
+            order + 1,
+        );
+        self.visit_cell(
+            statistics,
+            points,
+            (origin.0 + half_i.0 + j.0, origin.1 + half_i.1 + j.1),
+            (-half_j.0, -half_j.1),
+            (-half_i.0, -half_i.1),
+            order + 1,
+        );
+    }
+}
+
+impl<'a, T> BaseCurve<T> for AdaptiveHilbertCurve<'a, T>
+where
+    T: Float + Euclid,
+{
+    /// Segments between the cell centers, the image is stretched onto the unit square
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        let width = self.image.width() as f64;
+        let height = self.image.height() as f64;
+
+        let mut points = Vec::new();
+        self.visit_cell(
+            &Statistics::new(self.image),
+            &mut points,
+            (0.0, 0.0),
+            (width, 0.0),
+            (0.0, height),
+            0,
+        );
+        let points: Vec<(T, T)> = points
+            .iter()
+            .map(|(x, y)| (T::from(x / width).unwrap(), T::from(y / height).unwrap()))
+            .collect();
+        connect_points(&points)
+    }
+
+    /// The cells follow the image, so the unit square is stretched back onto it
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        let scale = (T::from(width).unwrap(), T::from(height).unwrap());
+        self.segments()
+            .iter()
+            .map(|(start, stop)| {
+                (
+                    (start.0 * scale.0, start.1 * scale.1),
+                    (stop.0 * scale.0, stop.1 * scale.1),
+                )
+            })
+            .collect()
+    }
+}
@@ -1,8 +1,68 @@
 use image::{ImageBuffer, Luma};
 
-use crate::{hilbert_curve, line_utils::{crop_to_scale, Length, partition_line, thicken_line, thicken_line_sin, thicken_lines_sin,  smooth_corners}, approximate};
+use crate::{base_curve::BaseCurve, modulation::{frequency_modulate_lines, loop_lines, modulate_lines, smooth_modulate_lines, Modulation}, line_utils::{Length, Line, partition_line, thicken_line, thicken_line_sin, thicken_lines_parallel, thicken_lines_sin,  smooth_corners}, approximate};
 
-pub fn approximate_image(image:&ImageBuffer<Luma<u8>,Vec<u8>>,order:usize,omega:f32,drop_bright:bool)->Vec<((f32, f32), (f32, f32))>{
+/// Function approximates the image by thickening the lines of the given base curve
+pub fn approximate_image<C>(image:&ImageBuffer<Luma<u8>,Vec<u8>>,curve:&C,omega:f32,drop_bright:bool)->Vec<((f32, f32), (f32, f32))>
+where
+    C: BaseCurve<f32> + ?Sized,
+{
+    let (lines, thicknesses, _) = thickness_field(image, curve);
+    thicken_lines_sin(&lines, &thicknesses, omega,drop_bright)
+}
+
+/// Function approximates the image by a stroke oscillating around the lines of the given base curve with the given modulation
+pub fn approximate_image_modulated<C>(image:&ImageBuffer<Luma<u8>,Vec<u8>>,curve:&C,modulation:&Modulation<f32>,drop_bright:bool)->Vec<Line<f32>>
+where
+    C: BaseCurve<f32> + ?Sized,
+{
+    let (lines, thicknesses, _) = thickness_field(image, curve);
+    modulate_lines(&lines, &thicknesses, modulation, drop_bright)
+}
+
+/// Function approximates the image like [approximate_image_modulated] with a stroke running smoothly through the corners of the base curve
+pub fn approximate_image_smooth_modulated<C>(image:&ImageBuffer<Luma<u8>,Vec<u8>>,curve:&C,modulation:&Modulation<f32>,drop_bright:bool)->Vec<Line<f32>>
+where
+    C: BaseCurve<f32> + ?Sized,
+{
+    let (lines, thicknesses, _) = thickness_field(image, curve);
+    smooth_modulate_lines(&lines, &thicknesses, modulation, drop_bright)
+}
+
+/// Function approximates the image by a stroke around the lines of the given base curve whose frequency rises with the darkness
+pub fn approximate_image_frequency_modulated<C>(image:&ImageBuffer<Luma<u8>,Vec<u8>>,curve:&C,modulation:&Modulation<f32>,drop_bright:bool)->Vec<Line<f32>>
+where
+    C: BaseCurve<f32> + ?Sized,
+{
+    let (lines, thicknesses, max_thickness) = thickness_field(image, curve);
+    frequency_modulate_lines(&lines, &thicknesses, max_thickness, modulation, drop_bright)
+}
+
+/// Function approximates the image by a stroke drawing loops around the lines of the given base curve, which grow with the darkness
+pub fn approximate_image_loops<C>(image:&ImageBuffer<Luma<u8>,Vec<u8>>,curve:&C,omega:f32,drop_bright:bool)->Vec<Line<f32>>
+where
+    C: BaseCurve<f32> + ?Sized,
+{
+    let (lines, thicknesses, _) = thickness_field(image, curve);
+    loop_lines(&lines, &thicknesses, omega, drop_bright)
+}
+
+/// Function approximates the image by parallel copies of the lines of the given base curve, `spacing` pixels apart, whose number grows with the darkness
+pub fn approximate_image_parallel<C>(image:&ImageBuffer<Luma<u8>,Vec<u8>>,curve:&C,spacing:f32,drop_bright:bool)->Vec<Line<f32>>
+where
+    C: BaseCurve<f32> + ?Sized,
+{
+    let (lines, thicknesses, _) = thickness_field(image, curve);
+    thicken_lines_parallel(&lines, &thicknesses, spacing, drop_bright)
+}
+
+/// Function places the base curve on the image, splits it into lines of one pixel and returns them
+/// with the thickness that best approximates the image around each line and the maximal thickness,
+/// at which the lines would cover the image completely
+pub fn thickness_field<C>(image:&ImageBuffer<Luma<u8>,Vec<u8>>,curve:&C)->(Vec<Line<f32>>, Vec<f32>, f32)
+where
+    C: BaseCurve<f32> + ?Sized,
+{
     let mut image=image.clone();
     let min=*image.iter().min().unwrap();
     let max=*image.iter().max().unwrap();
@@ -10,13 +70,13 @@ pub fn approximate_image(image:&ImageBuffer<Luma<u8>,Vec<u8>>,order:usize,omega:
         // heighten contrast
         *pixel=(((*pixel-min) as f32/(max-min) as f32)*255f32) as u8;
     }
-    let mut lines: Vec<((f32, f32), (f32, f32))> =
-        hilbert_curve::HilbertCurve::new(order)
-            .into_iter()
-            .collect();
-    crop_to_scale(&mut lines, image.width() as usize, image.height() as usize);
+    let mut lines = curve.fit_to_image(image.width() as usize, image.height() as usize);
     lines=smooth_corners(&lines);
     let total_length = lines.get_total_length();
+    if total_length <= 0f32 {
+        // e.g. a tsp curve on an image without dots or a path collapsed to a point
+        return (Vec::new(), Vec::new(), 0f32);
+    }
     let max_thickness = (image.width() * image.height()) as f32 / total_length;
     lines= lines
         .iter()
@@ -26,6 +86,5 @@ pub fn approximate_image(image:&ImageBuffer<Luma<u8>,Vec<u8>>,order:usize,omega:
     let thicknesses=lines.iter().map(|line|{
         approximate(&image, &line.0, &line.1, max_thickness)
     }).collect();
-    return thicken_lines_sin(&lines, &thicknesses, omega,drop_bright);
-    
-}
\ No newline at end of file
+    (lines, thicknesses, max_thickness)
+}
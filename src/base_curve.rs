@@ -0,0 +1,32 @@
+use num::{traits::Euclid, Float};
+
+use crate::line_utils::{crop_to_scale, Line};
+
+/// Path along which an image is approximated
+pub trait BaseCurve<T>
+where
+    T: Float + Euclid,
+{
+    /// Returns the ordered segments of the path in unit-square coordinates
+    fn segments(&self) -> Vec<Line<T>>;
+
+    /// Returns the segments of the path placed on an image of given size in pixel coordinates.
+    ///
+    /// By default the bounding box of the path is stretched to a square covering the larger image side
+    /// and segments outside of the image are dropped. A path without extent along an axis is centered on it.
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<Line<T>> {
+        let mut lines = self.segments();
+        crop_to_scale(&mut lines, width, height);
+        lines
+    }
+}
+
+/// Any list of segments can be used as path
+impl<T> BaseCurve<T> for Vec<Line<T>>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<Line<T>> {
+        self.clone()
+    }
+}
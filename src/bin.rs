@@ -1,14 +1,178 @@
-use clap::{Arg, Parser};
-use htree::HTree;
+use clap::{Parser, ValueEnum};
 use image::{ImageBuffer, Luma};
 use imageproc::drawing::draw_line_segment_mut;
 use line_approximator_lib::{
-    approximate, hilbert_curve,
-    line_utils::{crop_to_scale, partition_line, thicken_line, Length}, approximator::approximate_image,
+    adaptive_hilbert_curve::AdaptiveHilbertCurve,
+    approximator::{
+        approximate_image_frequency_modulated, approximate_image_loops,
+        approximate_image_modulated, approximate_image_parallel,
+        approximate_image_smooth_modulated,
+    },
+    base_curve::BaseCurve,
+    concentric_curve::{ConcentricCircles, ConcentricSquares},
+    gcode::{save_gcode, GcodeOptions, PenControl, Units},
+    generalized_hilbert_curve::GeneralizedHilbertCurve,
+    gosper_curve::GosperCurve,
+    h_tree_curve::HTreeCurve,
+    hatching::{hatch, HatchLayer},
+    hilbert_curve::HilbertCurve,
+    hpgl::{save_hpgl, HpglOptions, PageSize},
+    iso_contour::{iso_contours, iso_levels},
+    line_utils::Line,
+    modulation::{Modulation, Waveform},
+    moore_curve::MooreCurve,
+    parametric_curve::{HarmonographCurve, LissajousCurve, Pendulum},
+    peano_curve::PeanoCurve,
+    scanline_curve::ScanlineCurve,
+    sierpinski_curve::SierpinskiCurve,
+    spiral_curve::SpiralCurve,
+    stipple::{draw_dots, DotShape, Stipple},
+    streamline_curve::{StreamlineCurve, StreamlineDirection},
+    svg::{save_svg, SvgOptions},
+    tsp_curve::TspCurve,
+    z_order_curve::ZOrderCurve,
 };
-use std::env;
+use std::path::Path;
 
-/// Program to approximate image with an HTree
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Png,
+    Svg,
+    Gcode,
+    Hpgl,
+}
+
+impl Format {
+    fn from_extension(path: &str) -> Format {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase());
+        match extension.as_deref() {
+            Some("svg") => Format::Svg,
+            Some("gcode") | Some("gc") | Some("nc") => Format::Gcode,
+            Some("hpgl") | Some("hpg") | Some("plt") => Format::Hpgl,
+            _ => Format::Png,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Style {
+    /// base curve thickened according to the image brightness
+    Curve,
+    /// brightness iso-lines like a topographic map
+    Contours,
+    /// dots whose density follows the image darkness
+    Stipple,
+    /// up to four layers of hatch lines, 2^order lines along the larger image side
+    Hatch,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Renderer {
+    /// darker regions get a wider stroke
+    Amplitude,
+    /// like amplitude, but the stroke runs smoothly through the corners of the base curve
+    Smooth,
+    /// darker regions get a denser stroke of constant width
+    Frequency,
+    /// the stroke draws loops, which grow in darker regions, ignores the waveform
+    Loops,
+    /// parallel copies of the base curve, more of them in darker regions, instead of an oscillating stroke
+    Parallel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Wave {
+    Sine,
+    Triangle,
+    /// meander
+    Square,
+    Sawtooth,
+}
+
+impl From<Wave> for Waveform {
+    fn from(wave: Wave) -> Self {
+        match wave {
+            Wave::Sine => Waveform::Sine,
+            Wave::Triangle => Waveform::Triangle,
+            Wave::Square => Waveform::Square,
+            Wave::Sawtooth => Waveform::Sawtooth,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Dot {
+    Circle,
+    Segment,
+}
+
+impl From<Dot> for DotShape {
+    fn from(dot: Dot) -> Self {
+        match dot {
+            Dot::Circle => DotShape::Circle,
+            Dot::Segment => DotShape::Segment,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum CurveKind {
+    Hilbert,
+    Htree,
+    Peano,
+    Moore,
+    Gosper,
+    /// generalized hilbert curve with 2^order cells along the larger image side
+    Gilbert,
+    /// hilbert curve whose order adapts to the image detail, up to the given order
+    Adaptive,
+    /// archimedean spiral with 2^order turns, see --turns
+    Spiral,
+    /// serpentine scan lines, 2^order lines along the larger image side, see --line-spacing
+    Scanline,
+    /// 2^order concentric circles
+    Circles,
+    /// 2^order concentric squares
+    Squares,
+    /// closed sierpinski curve
+    Sierpinski,
+    /// z-order (morton) curve
+    Zorder,
+    /// lissajous figure sampled into 2^order segments, at least 16 per period
+    Lissajous,
+    /// harmonograph of two damped pendulums sampled into 2^order segments, at least 16 per period
+    Harmonograph,
+    /// streamlines along lines of equal brightness, 2^order lines along the larger image side
+    Contour,
+    /// streamlines along the brightness gradient, 2^order lines along the larger image side
+    Gradient,
+    /// shortest path found through stipple dots
+    Tsp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Page {
+    A4,
+    A3,
+    Letter,
+    Tabloid,
+}
+
+impl From<Page> for PageSize {
+    fn from(page: Page) -> Self {
+        match page {
+            Page::A4 => PageSize::A4,
+            Page::A3 => PageSize::A3,
+            Page::Letter => PageSize::Letter,
+            Page::Tabloid => PageSize::Tabloid,
+        }
+    }
+}
+
+/// Program to approximate an image by lines along a base curve and export them as png, svg, g-code or hpgl
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
@@ -16,26 +180,353 @@ struct Args {
     input: String,
 
     /// Number of fractal order
+    #[arg(value_parser = clap::value_parser!(u8).range(1..))]
     order: u8,
 
+    /// Name of output file
     output: String,
+
+    /// Drawing style
+    #[arg(long, value_enum, default_value_t = Style::Curve)]
+    style: Style,
+
+    /// Base curve the image is approximated with
+    #[arg(long, value_enum, default_value_t = CurveKind::Hilbert)]
+    curve: CurveKind,
+
+    /// How the darkness is encoded in the stroke oscillating around the base curve
+    #[arg(long, value_enum, default_value_t = Renderer::Amplitude)]
+    renderer: Renderer,
+
+    /// Waveform of the stroke oscillating around the base curve
+    #[arg(long, value_enum, default_value_t = Wave::Sine)]
+    waveform: Wave,
+
+    /// Angular frequency of the waveform per pixel along the base curve, reached in the darkest regions by the frequency renderer
+    #[arg(long, default_value_t = 1.0)]
+    omega: f32,
+
+    /// Fraction of each period the waveform spends on the positive side of the base curve
+    #[arg(long, default_value_t = 0.5)]
+    duty_cycle: f32,
+
+    /// Distance in pixels between the copies of the base curve drawn by the parallel renderer, e.g. the laser line width
+    #[arg(long, default_value_t = 1.0)]
+    spacing: f32,
+
+    /// Minimal order of the adaptive curve
+    #[arg(long, default_value_t = 2)]
+    min_order: u8,
+
+    /// Standard deviation of brightness (0-255) above which the adaptive curve subdivides a cell
+    #[arg(long, default_value_t = 20.0)]
+    threshold: f64,
+
+    /// Horizontal center of spiral and concentric curves as fraction of the image width
+    #[arg(long, default_value_t = 0.5)]
+    center_x: f32,
+
+    /// Vertical center of spiral and concentric curves as fraction of the image height
+    #[arg(long, default_value_t = 0.5)]
+    center_y: f32,
+
+    /// Wind the spiral counter clockwise
+    #[arg(long)]
+    counter_clockwise: bool,
+
+    /// Number of turns of the spiral, defaults to 2^order
+    #[arg(long)]
+    turns: Option<usize>,
+
+    /// Angle of the scan lines to the x axis in degrees
+    #[arg(long, default_value_t = 0.0)]
+    angle: f32,
+
+    /// Distance between the scan lines as fraction of the larger image side, defaults to 1/2^order
+    #[arg(long)]
+    line_spacing: Option<f32>,
+
+    /// Frequency of the x oscillation of lissajous and harmonograph curves
+    #[arg(long, default_value_t = 3.0)]
+    freq_x: f32,
+
+    /// Frequency of the y oscillation of lissajous and harmonograph curves
+    #[arg(long, default_value_t = 2.0)]
+    freq_y: f32,
+
+    /// Phase of the x oscillation of lissajous and harmonograph curves in degrees
+    #[arg(long, default_value_t = 90.0)]
+    phase: f32,
+
+    /// Damping of the harmonograph pendulums, the curve is drawn until they decay to 1% of their amplitude
+    #[arg(long, default_value_t = 0.02)]
+    damping: f32,
+
+    /// Number of brightness levels of the contours style
+    #[arg(long, default_value_t = 8)]
+    levels: usize,
+
+    /// Exponent spacing the levels of the contours style, values above 1 space darker levels more densely
+    #[arg(long, default_value_t = 1.0)]
+    dark_density: f32,
+
+    /// Number of hatch directions (1-4) of the hatch style, each drawn in darker regions than the previous one
+    #[arg(long, default_value_t = 4)]
+    layers: usize,
+
+    /// Number of dots of the stipple style and tsp curve
+    #[arg(long, default_value_t = 5000)]
+    points: usize,
+
+    /// Number of relaxation steps spreading the dots of the stipple style and tsp curve
+    #[arg(long, default_value_t = 20)]
+    iterations: usize,
+
+    /// Seed of the random placement of the dots of the stipple style and tsp curve
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Shape of the stipple dots
+    #[arg(long, value_enum, default_value_t = Dot::Circle)]
+    dot: Dot,
+
+    /// Radius of the stipple dots in pixels
+    #[arg(long, default_value_t = 1.0)]
+    dot_radius: f32,
+
+    /// Output format, derived from the extension of the output file if omitted
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Stroke width of svg output in pixels of the input image
+    #[arg(long, default_value_t = 1.0)]
+    stroke_width: f64,
+
+    /// Width of svg document and g-code drawing in mm
+    #[arg(long, default_value_t = 210.0)]
+    width_mm: f64,
+
+    /// Height of svg document in mm, follows the aspect ratio of the input image if omitted
+    #[arg(long)]
+    height_mm: Option<f64>,
+
+    /// Feed rate of g-code drawing moves in mm per minute, converted with --inches
+    #[arg(long, default_value_t = 1000.0)]
+    feed_rate: f64,
+
+    /// Z height of lifted pen in g-code output in mm, converted with --inches
+    #[arg(long, default_value_t = 5.0)]
+    pen_up: f64,
+
+    /// Z height of lowered pen in g-code output in mm, converted with --inches
+    #[arg(long, default_value_t = 0.0)]
+    pen_down: f64,
+
+    /// Control pen with servo commands (M3 S<power> / M5) instead of z moves in g-code output
+    #[arg(long)]
+    servo: Option<u32>,
+
+    /// Use inches instead of mm in g-code output, lengths and feed rates given in mm are converted
+    #[arg(long)]
+    inches: bool,
+
+    /// Machine x coordinate of the lower left image corner in g-code output in mm
+    #[arg(long, default_value_t = 0.0)]
+    origin_x: f64,
+
+    /// Machine y coordinate of the lower left image corner in g-code output in mm
+    #[arg(long, default_value_t = 0.0)]
+    origin_y: f64,
+
+    /// Page size of hpgl output
+    #[arg(long, value_enum, default_value_t = Page::A4)]
+    page: Page,
+
+    /// Pen selected in hpgl output
+    #[arg(long, default_value_t = 1)]
+    pen: u8,
+}
+
+fn base_curve<'a>(
+    args: &Args,
+    image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+) -> Box<dyn BaseCurve<f32> + 'a> {
+    let order = args.order as usize;
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    match args.curve {
+        CurveKind::Hilbert => Box::new(HilbertCurve::new(order)),
+        CurveKind::Htree => Box::new(HTreeCurve::new(order)),
+        CurveKind::Peano => Box::new(PeanoCurve::new(order)),
+        CurveKind::Moore => Box::new(MooreCurve::new(order)),
+        CurveKind::Gosper => Box::new(GosperCurve::new(order)),
+        CurveKind::Gilbert => Box::new(GeneralizedHilbertCurve::fitting(width, height, 1 << order)),
+        CurveKind::Adaptive => Box::new(AdaptiveHilbertCurve::new(
+            image,
+            args.min_order as usize,
+            order,
+            args.threshold,
+        )),
+        CurveKind::Spiral => Box::new(SpiralCurve::new(
+            args.turns.unwrap_or(1 << order),
+            (args.center_x, args.center_y),
+            !args.counter_clockwise,
+        )),
+        CurveKind::Scanline => Box::new(ScanlineCurve::new(
+            args.line_spacing.unwrap_or(1. / (1 << order) as f32),
+            args.angle.to_radians(),
+        )),
+        CurveKind::Circles => Box::new(ConcentricCircles::new(
+            1 << order,
+            (args.center_x, args.center_y),
+        )),
+        CurveKind::Squares => Box::new(ConcentricSquares::new(
+            1 << order,
+            (args.center_x, args.center_y),
+        )),
+        CurveKind::Sierpinski => Box::new(SierpinskiCurve::new(order)),
+        CurveKind::Zorder => Box::new(ZOrderCurve::new(order)),
+        CurveKind::Lissajous => Box::new(LissajousCurve::new(
+            (args.freq_x, args.freq_y),
+            args.phase.to_radians(),
+            1 << order,
+        )),
+        CurveKind::Harmonograph => {
+            let damping = args.damping.max(1e-3);
+            Box::new(HarmonographCurve::new(
+                vec![Pendulum::new(
+                    1.,
+                    args.freq_x,
+                    args.phase.to_radians(),
+                    damping,
+                )],
+                vec![Pendulum::new(1., args.freq_y, 0., damping)],
+                100f32.ln() / damping,
+                1 << order,
+            ))
+        }
+        CurveKind::Contour => Box::new(StreamlineCurve::new(
+            image,
+            1. / (1 << order) as f32,
+            StreamlineDirection::Contour,
+        )),
+        CurveKind::Gradient => Box::new(StreamlineCurve::new(
+            image,
+            1. / (1 << order) as f32,
+            StreamlineDirection::Gradient,
+        )),
+        CurveKind::Tsp => Box::new(TspCurve::new(
+            image,
+            args.points,
+            args.iterations,
+            args.seed,
+        )),
+    }
+}
+
+fn save_png(lines: &[Line<f32>], width: u32, height: u32, path: &str) {
+    let mut approximated_image: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::new(width, height);
+    approximated_image.fill(255u8);
+    let black = Luma([0u8]);
+    for (start, stop) in lines {
+        draw_line_segment_mut(&mut approximated_image, *start, *stop, black)
+    }
+    approximated_image.save(path).unwrap();
 }
 
 pub fn main() {
     let args = Args::parse();
-    let image = match image::open(args.input) {
+    let image = match image::open(&args.input) {
         Ok(image) => image.to_luma8(),
         Err(err) => panic!("File could not be opened {:?}", err),
     };
-    
-    let mut approximated_image: ImageBuffer<Luma<u8>, Vec<u8>> =
-        ImageBuffer::new(image.width(), image.height());
-    approximated_image.fill(255u8);
-    let black = Luma([0u8]);
+    let (width, height) = (image.width(), image.height());
 
-    let lines =approximate_image(&image, args.order as usize,1f32,false);
-    for (start, stop) in lines {
-        draw_line_segment_mut(&mut approximated_image, start, stop, black)
+    let lines = match args.style {
+        Style::Curve => {
+            let curve = base_curve(&args, &image);
+            let modulation =
+                Modulation::new(args.waveform.into(), args.omega).with_duty_cycle(args.duty_cycle);
+            match args.renderer {
+                Renderer::Amplitude => {
+                    approximate_image_modulated(&image, curve.as_ref(), &modulation, false)
+                }
+                Renderer::Smooth => {
+                    approximate_image_smooth_modulated(&image, curve.as_ref(), &modulation, false)
+                }
+                Renderer::Frequency => approximate_image_frequency_modulated(
+                    &image,
+                    curve.as_ref(),
+                    &modulation,
+                    false,
+                ),
+                Renderer::Loops => {
+                    approximate_image_loops(&image, curve.as_ref(), args.omega, false)
+                }
+                Renderer::Parallel => {
+                    approximate_image_parallel(&image, curve.as_ref(), args.spacing, false)
+                }
+            }
+        }
+        Style::Contours => iso_contours(
+            &image,
+            &iso_levels(&image, args.levels, args.dark_density),
+            1f32,
+        )
+        .concat(),
+        Style::Stipple => draw_dots(
+            &Stipple::new(&image, args.points, args.iterations, args.seed).points(),
+            args.dot_radius,
+            args.dot.into(),
+        ),
+        Style::Hatch => hatch(
+            &image,
+            &HatchLayer::layers(args.layers),
+            1. / (1 << args.order) as f32,
+        )
+        .concat(),
+    };
+
+    match args
+        .format
+        .unwrap_or_else(|| Format::from_extension(&args.output))
+    {
+        Format::Png => save_png(&lines, width, height, &args.output),
+        Format::Svg => {
+            let mut options = SvgOptions::with_width(args.width_mm, width, height);
+            if let Some(height_mm) = args.height_mm {
+                options.height_mm = height_mm;
+            }
+            options.stroke_width = args.stroke_width;
+            save_svg(&args.output, &lines, width, height, &options).unwrap();
+        }
+        Format::Gcode => {
+            let units = if args.inches {
+                Units::Inches
+            } else {
+                Units::Millimeters
+            };
+            // lengths and feed rates are given in mm
+            let mm = match units {
+                Units::Millimeters => 1.0,
+                Units::Inches => 1.0 / 25.4,
+            };
+            let mut options = GcodeOptions::new(args.width_mm * mm / width as f64);
+            options.units = units;
+            options.feed_rate = args.feed_rate * mm;
+            options.origin = (args.origin_x * mm, args.origin_y * mm);
+            options.pen = match args.servo {
+                Some(power) => PenControl::Servo { power, dwell: 0.2 },
+                None => PenControl::ZAxis {
+                    up: args.pen_up * mm,
+                    down: args.pen_down * mm,
+                    feed_rate: args.feed_rate * mm,
+                },
+            };
+            save_gcode(&args.output, &lines, height, &options).unwrap();
+        }
+        Format::Hpgl => {
+            let options = HpglOptions::new(args.page.into(), args.pen);
+            save_hpgl(&args.output, &lines, width, height, &options).unwrap();
+        }
     }
-    approximated_image.save(args.output).unwrap();
 }
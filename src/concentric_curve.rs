@@ -0,0 +1,203 @@
+use num::{traits::Euclid, Float};
+
+use crate::{
+    base_curve::BaseCurve,
+    line_utils::{clip, connect_points, LineIterator},
+};
+
+/// Concentric circles around a center, each circle joined to the next by a short radial segment
+///
+/// The outermost circle reaches the image corner farthest from the center, so the circles cover the whole image.
+/// They are clipped to the image and stay one path up to the closest border.
+#[derive(Clone, Copy, Debug)]
+pub struct ConcentricCircles<T> {
+    rings: usize,
+    center: (T, T),
+}
+
+/// Concentric squares around a center, each square joined to the next by a short horizontal segment
+///
+/// The outermost square reaches the image border farthest from the center, so the squares cover the whole image.
+/// They are clipped to the image and stay one path up to the closest border.
+#[derive(Clone, Copy, Debug)]
+pub struct ConcentricSquares<T> {
+    rings: usize,
+    center: (T, T),
+}
+
+impl<T> ConcentricCircles<T>
+where
+    T: Float,
+{
+    /// Returns the given number of circles around `center`, given as fraction of the image width and height and
+    /// clamped to the image
+    pub fn new(rings: usize, center: (T, T)) -> ConcentricCircles<T> {
+        ConcentricCircles {
+            rings: rings.max(1),
+            center: clamp_center(center),
+        }
+    }
+
+    /// Returns the unclipped circles on an image of given size, each circle starts and ends right of the center
+    fn loops(&self, width: T, height: T) -> Vec<Vec<(T, T)>> {
+        let two_pi = T::from(2f64 * std::f64::consts::PI).unwrap();
+        let center = (self.center.0 * width, self.center.1 * height);
+        let (x, y) = farthest_border(center, width, height);
+        let spacing = x.hypot(y) / T::from(self.rings).unwrap();
+
+        (1..=self.rings)
+            .map(|ring| {
+                let radius = spacing * T::from(ring).unwrap();
+                // chords are at most half the spacing long and span at most 1/32 of a turn
+                let num = (two_pi * radius / (spacing / T::from(2).unwrap()))
+                    .ceil()
+                    .max(T::from(32).unwrap())
+                    .to_usize()
+                    .unwrap();
+                (0..=num)
+                    .map(|i| two_pi * T::from(i).unwrap() / T::from(num).unwrap())
+                    .map(|angle| {
+                        (
+                            center.0 + radius * angle.cos(),
+                            center.1 + radius * angle.sin(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<T> ConcentricSquares<T>
+where
+    T: Float + Euclid,
+{
+    /// Returns the given number of squares around `center`, given as fraction of the image width and height and
+    /// clamped to the image
+    pub fn new(rings: usize, center: (T, T)) -> ConcentricSquares<T> {
+        ConcentricSquares {
+            rings: rings.max(1),
+            center: clamp_center(center),
+        }
+    }
+
+    /// Returns the unclipped squares on an image of given size, each square starts and ends right of the center
+    fn loops(&self, width: T, height: T) -> Vec<Vec<(T, T)>> {
+        let center = (self.center.0 * width, self.center.1 * height);
+        let (x, y) = farthest_border(center, width, height);
+        let spacing = x.max(y) / T::from(self.rings).unwrap();
+
+        (1..=self.rings)
+            .map(|ring| {
+                let half_side = spacing * T::from(ring).unwrap();
+                vec![
+                    (center.0 + half_side, center.1),
+                    (center.0 + half_side, center.1 + half_side),
+                    (center.0 - half_side, center.1 + half_side),
+                    (center.0 - half_side, center.1 - half_side),
+                    (center.0 + half_side, center.1 - half_side),
+                    (center.0 + half_side, center.1),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Function clamps a center given as fraction of the image width and height to the image
+fn clamp_center<T>(center: (T, T)) -> (T, T)
+where
+    T: Float,
+{
+    let clamp = |value: T| value.max(T::zero()).min(T::one());
+    (clamp(center.0), clamp(center.1))
+}
+
+/// Function returns the horizontal and vertical distance of `center` to the farthest image borders
+fn farthest_border<T>(center: (T, T), width: T, height: T) -> (T, T)
+where
+    T: Float,
+{
+    (
+        center.0.max(width - center.0),
+        center.1.max(height - center.1),
+    )
+}
+
+/// Function joins closed loops, each loop ends where the connection to the next one starts,
+/// and clips them to an image of given size
+fn join_loops<T>(loops: &[Vec<(T, T)>], width: usize, height: usize) -> Vec<((T, T), (T, T))>
+where
+    T: Float + Euclid,
+{
+    let mut lines = Vec::new();
+    for (i, points) in loops.iter().enumerate() {
+        if i > 0 {
+            lines.push((*loops[i - 1].last().unwrap(), points[0]));
+        }
+        lines.extend(connect_points(points));
+    }
+    clip(&mut lines, width, height);
+    lines
+}
+
+impl<T> IntoIterator for ConcentricCircles<T>
+where
+    T: Float + Euclid,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    /// Iterates over the circles and their connections on the unit square
+    fn into_iter(self) -> Self::IntoIter {
+        join_loops(&self.loops(T::one(), T::one()), 1, 1).into()
+    }
+}
+
+impl<T> IntoIterator for ConcentricSquares<T>
+where
+    T: Float + Euclid,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    /// Iterates over the squares and their connections on the unit square
+    fn into_iter(self) -> Self::IntoIter {
+        join_loops(&self.loops(T::one(), T::one()), 1, 1).into()
+    }
+}
+
+impl<T> BaseCurve<T> for ConcentricCircles<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// The circles are laid out on the image itself to stay round
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        join_loops(
+            &self.loops(T::from(width).unwrap(), T::from(height).unwrap()),
+            width,
+            height,
+        )
+    }
+}
+
+impl<T> BaseCurve<T> for ConcentricSquares<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// The squares are laid out on the image itself to stay square
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        join_loops(
+            &self.loops(T::from(width).unwrap(), T::from(height).unwrap()),
+            width,
+            height,
+        )
+    }
+}
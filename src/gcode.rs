@@ -0,0 +1,137 @@
+use std::{fmt::Write, fs, io, path::Path};
+
+use num::{traits::Euclid, Float};
+
+use crate::line_utils::{join_lines, Line};
+
+/// Commands used to lift and lower the pen
+#[derive(Clone, Copy, Debug)]
+pub enum PenControl {
+    /// pen is lifted and lowered by moving the z axis to the given heights
+    ZAxis { up: f64, down: f64, feed_rate: f64 },
+    /// pen is lowered by a servo with M3 S<power> and lifted with M5, dwelling the given seconds after each command
+    Servo { power: u32, dwell: f64 },
+}
+
+/// Units of the machine coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Units {
+    Millimeters,
+    Inches,
+}
+
+/// Options of the generated g-code program
+#[derive(Clone, Copy, Debug)]
+pub struct GcodeOptions {
+    pub pen: PenControl,
+    /// feed rate while drawing in units per minute
+    pub feed_rate: f64,
+    /// units of all lengths and feed rates of the options
+    pub units: Units,
+    /// machine coordinates of the image origin, the lower left corner if flip_y is set
+    pub origin: (f64, f64),
+    /// units per image pixel
+    pub scale: f64,
+    /// image y axis points down, machine y axis points up -> flip image vertically
+    pub flip_y: bool,
+    /// maximal distance between the end of a line and the start of the next one to draw them without lifting the pen
+    pub join_tolerance: f64,
+}
+
+impl GcodeOptions {
+    /// Returns options for a machine in mm with a z axis pen, scaling each image pixel to `scale` mm
+    pub fn new(scale: f64) -> GcodeOptions {
+        GcodeOptions {
+            pen: PenControl::ZAxis {
+                up: 5.0,
+                down: 0.0,
+                feed_rate: 500.0,
+            },
+            feed_rate: 1000.0,
+            units: Units::Millimeters,
+            origin: (0.0, 0.0),
+            scale,
+            flip_y: true,
+            join_tolerance: 1e-3,
+        }
+    }
+
+    fn pen_up(&self) -> String {
+        match self.pen {
+            PenControl::ZAxis { up, .. } => format!("G0 Z{:.3}", up),
+            PenControl::Servo { dwell, .. } => format!("M5\nG4 P{:.3}", dwell),
+        }
+    }
+
+    fn pen_down(&self) -> String {
+        match self.pen {
+            PenControl::ZAxis {
+                down, feed_rate, ..
+            } => format!("G1 Z{:.3} F{:.1}", down, feed_rate),
+            PenControl::Servo { power, dwell } => format!("M3 S{}\nG4 P{:.3}", power, dwell),
+        }
+    }
+
+    fn machine_position<T>(&self, point: &(T, T), height: u32) -> (f64, f64)
+    where
+        T: Float,
+    {
+        let x = point.0.to_f64().unwrap();
+        let y = point.1.to_f64().unwrap();
+        let y = if self.flip_y { height as f64 - y } else { y };
+        (
+            self.origin.0 + x * self.scale,
+            self.origin.1 + y * self.scale,
+        )
+    }
+}
+
+/// Function writes lines as g-code program, lines of an image of given height are drawn as G1 moves and connected by G0 moves with lifted pen
+pub fn to_gcode<T>(lines: &[Line<T>], height: u32, options: &GcodeOptions) -> String
+where
+    T: Float + Euclid,
+{
+    let mut gcode = String::new();
+    match options.units {
+        Units::Millimeters => writeln!(gcode, "G21").unwrap(),
+        Units::Inches => writeln!(gcode, "G20").unwrap(),
+    }
+    writeln!(gcode, "G90").unwrap();
+    writeln!(gcode, "{}", options.pen_up()).unwrap();
+    for polyline in join_lines(lines, T::from(options.join_tolerance).unwrap()) {
+        let (x, y) = options.machine_position(&polyline[0], height);
+        writeln!(gcode, "G0 X{:.3} Y{:.3}", x, y).unwrap();
+        writeln!(gcode, "{}", options.pen_down()).unwrap();
+        for (i, point) in polyline.iter().skip(1).enumerate() {
+            let (x, y) = options.machine_position(point, height);
+            if i == 0 {
+                writeln!(gcode, "G1 X{:.3} Y{:.3} F{:.1}", x, y, options.feed_rate).unwrap();
+            } else {
+                writeln!(gcode, "G1 X{:.3} Y{:.3}", x, y).unwrap();
+            }
+        }
+        writeln!(gcode, "{}", options.pen_up()).unwrap();
+    }
+    writeln!(
+        gcode,
+        "G0 X{:.3} Y{:.3}",
+        options.origin.0, options.origin.1
+    )
+    .unwrap();
+    writeln!(gcode, "M2").unwrap();
+    gcode
+}
+
+/// Function saves lines as g-code program, see [to_gcode]
+pub fn save_gcode<T, P>(
+    path: P,
+    lines: &[Line<T>],
+    height: u32,
+    options: &GcodeOptions,
+) -> io::Result<()>
+where
+    T: Float + Euclid,
+    P: AsRef<Path>,
+{
+    fs::write(path, to_gcode(lines, height, options))
+}
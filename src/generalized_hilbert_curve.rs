@@ -0,0 +1,177 @@
+use num::{traits::Euclid, Float};
+use std::marker::PhantomData;
+
+use crate::{
+    base_curve::BaseCurve,
+    line_utils::{connect_points, LineIterator},
+};
+
+/// Generalized Hilbert curve filling a grid of arbitrary width and height with one continuous path,
+/// see <https://github.com/jakubcerveny/gilbert>
+///
+/// Consecutive cells are neighbours, only grids with odd width and even height (or vice versa) need a single diagonal step.
+#[derive(Clone, Copy, Debug)]
+pub struct GeneralizedHilbertCurve<T> {
+    width: usize,
+    height: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> GeneralizedHilbertCurve<T>
+where
+    T: Float,
+{
+    /// Returns a curve through a grid of width x height cells
+    pub fn new(width: usize, height: usize) -> GeneralizedHilbertCurve<T> {
+        GeneralizedHilbertCurve {
+            width: width.max(1),
+            height: height.max(1),
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Returns a curve with `resolution` cells along the larger side of an image of given size,
+    /// the number of cells along the other side follows the aspect ratio of the image
+    pub fn fitting(
+        image_width: usize,
+        image_height: usize,
+        resolution: usize,
+    ) -> GeneralizedHilbertCurve<T> {
+        let side = image_width.max(image_height) as f64;
+        GeneralizedHilbertCurve::new(
+            (resolution as f64 * image_width as f64 / side).round() as usize,
+            (resolution as f64 * image_height as f64 / side).round() as usize,
+        )
+    }
+
+    /// Returns the visited grid cells in order
+    fn cells(&self) -> Vec<(i64, i64)> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        let mut cells = Vec::with_capacity(self.width * self.height);
+        if width >= height {
+            generate(&mut cells, (0, 0), (width, 0), (0, height));
+        } else {
+            generate(&mut cells, (0, 0), (0, height), (width, 0));
+        }
+        cells
+    }
+}
+
+/// Appends the cells of the rectangle spanned from `position` by the major axis `a` and the minor axis `b`
+fn generate(cells: &mut Vec<(i64, i64)>, position: (i64, i64), a: (i64, i64), b: (i64, i64)) {
+    let width = (a.0 + a.1).abs();
+    let height = (b.0 + b.1).abs();
+    let direction_a = (a.0.signum(), a.1.signum());
+    let direction_b = (b.0.signum(), b.1.signum());
+
+    if height == 1 {
+        cells.extend((0..width).map(|i| {
+            (
+                position.0 + i * direction_a.0,
+                position.1 + i * direction_a.1,
+            )
+        }));
+        return;
+    }
+    if width == 1 {
+        cells.extend((0..height).map(|i| {
+            (
+                position.0 + i * direction_b.0,
+                position.1 + i * direction_b.1,
+            )
+        }));
+        return;
+    }
+
+    let mut a_half = (a.0.div_euclid(2), a.1.div_euclid(2));
+    let mut b_half = (b.0.div_euclid(2), b.1.div_euclid(2));
+    if 2 * width > 3 * height {
+        // long rectangle -> split in two along the major axis
+        if (a_half.0 + a_half.1).abs() % 2 == 1 && width > 2 {
+            a_half = (a_half.0 + direction_a.0, a_half.1 + direction_a.1);
+        }
+        generate(cells, position, a_half, b);
+        generate(
+            cells,
+            (position.0 + a_half.0, position.1 + a_half.1),
+            (a.0 - a_half.0, a.1 - a_half.1),
+            b,
+        );
+    } else {
+        // split in three: up along the minor axis, across, and back down
+        if (b_half.0 + b_half.1).abs() % 2 == 1 && height > 2 {
+            b_half = (b_half.0 + direction_b.0, b_half.1 + direction_b.1);
+        }
+        generate(cells, position, b_half, a_half);
+        generate(
+            cells,
+            (position.0 + b_half.0, position.1 + b_half.1),
+            a,
+            (b.0 - b_half.0, b.1 - b_half.1),
+        );
+        generate(
+            cells,
+            (
+                position.0 + (a.0 - direction_a.0) + (b_half.0 - direction_b.0),
+                position.1 + (a.1 - direction_a.1) + (b_half.1 - direction_b.1),
+            ),
+            (-b_half.0, -b_half.1),
+            (-(a.0 - a_half.0), -(a.1 - a_half.1)),
+        );
+    }
+}
+
+impl<T> IntoIterator for GeneralizedHilbertCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    /// Iterates over segments between cell centers in unit-square coordinates, the grid is centered in the unit square
+    fn into_iter(self) -> Self::IntoIter {
+        let side = T::from(self.width.max(self.height)).unwrap();
+        let offset = (
+            (side - T::from(self.width).unwrap()) / T::from(2).unwrap(),
+            (side - T::from(self.height).unwrap()) / T::from(2).unwrap(),
+        );
+        let half = T::from(0.5).unwrap();
+        let points: Vec<(T, T)> = self
+            .cells()
+            .iter()
+            .map(|(x, y)| {
+                (
+                    (T::from(*x).unwrap() + half + offset.0) / side,
+                    (T::from(*y).unwrap() + half + offset.1) / side,
+                )
+            })
+            .collect();
+        connect_points(&points).into()
+    }
+}
+
+impl<T> BaseCurve<T> for GeneralizedHilbertCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// The grid is stretched onto the whole image, so no segment gets cropped
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        let half = T::from(0.5).unwrap();
+        let scale = (
+            T::from(width).unwrap() / T::from(self.width).unwrap(),
+            T::from(height).unwrap() / T::from(self.height).unwrap(),
+        );
+        let to_image = |(x, y): (i64, i64)| {
+            (
+                (T::from(x).unwrap() + half) * scale.0,
+                (T::from(y).unwrap() + half) * scale.1,
+            )
+        };
+        let points: Vec<(T, T)> = self.cells().into_iter().map(to_image).collect();
+        connect_points(&points)
+    }
+}
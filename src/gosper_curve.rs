@@ -0,0 +1,64 @@
+use num::{traits::Euclid, Float};
+use std::{collections::HashMap, marker::PhantomData};
+
+use crate::{
+    base_curve::BaseCurve,
+    l_system,
+    line_utils::{connect_points, cover_image, LineIterator},
+};
+
+/// Gosper curve (flowsnake), a space filling curve on a hexagonal grid, see <https://en.wikipedia.org/wiki/Gosper_curve>
+///
+/// The curve consists of 7^order segments turning by multiples of 60°.
+#[derive(Clone, Copy, Debug)]
+pub struct GosperCurve<T> {
+    order: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> GosperCurve<T>
+where
+    T: Float,
+{
+    pub fn new(order: usize) -> GosperCurve<T> {
+        GosperCurve {
+            order,
+            _marker: PhantomData {},
+        }
+    }
+}
+
+impl<T> IntoIterator for GosperCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let rules = HashMap::from([('A', "A-B--B+A++AA+B-"), ('B', "+A-BB--B-A++A+B")]);
+        let commands = l_system::expand("A", &rules, self.order);
+        let points = l_system::to_unit_square(&l_system::turtle(&commands, 60.0, &['A', 'B']));
+        let points: Vec<(T, T)> = points
+            .iter()
+            .map(|(x, y)| (T::from(*x).unwrap(), T::from(*y).unwrap()))
+            .collect();
+        connect_points(&points).into()
+    }
+}
+
+impl<T> BaseCurve<T> for GosperCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// Stretching would distort the 60° angles, the curve is scaled uniformly to cover the image instead
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        let mut lines = self.segments();
+        cover_image(&mut lines, width, height);
+        lines
+    }
+}
@@ -0,0 +1,92 @@
+use num::{traits::Euclid, Float};
+use std::marker::PhantomData;
+
+use crate::{
+    base_curve::BaseCurve,
+    line_utils::{Line, LineIterator},
+};
+
+/// Each order of the H tree shrinks the segments by this factor
+const SCALE: f64 = std::f64::consts::FRAC_1_SQRT_2;
+
+/// H tree traversed depth first as one continuous path, see <https://en.wikipedia.org/wiki/H_tree>
+///
+/// The path starts at the left end of the horizontal root segment and ends at its right end.
+/// Every segment below the root is drawn twice, once on the way into a branch and once on the way back.
+#[derive(Clone, Copy, Debug)]
+pub struct HTreeCurve<T> {
+    order: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> HTreeCurve<T>
+where
+    T: Float,
+{
+    /// Returns the curve of given order, order 0 is only the root segment without extent and is raised to 1
+    pub fn new(order: usize) -> HTreeCurve<T> {
+        HTreeCurve {
+            order: order.max(1),
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Appends the tour through the branch whose segment of given length and direction is centered at `center`.
+    /// The tour starts and ends at `center`.
+    fn visit_branch(
+        &self,
+        segments: &mut Vec<Line<T>>,
+        center: (T, T),
+        direction: (T, T),
+        length: T,
+        order: usize,
+    ) {
+        if order > self.order {
+            return;
+        }
+        let half = length / T::from(2).unwrap();
+        let first = (center.0 - direction.0 * half, center.1 - direction.1 * half);
+        let second = (center.0 + direction.0 * half, center.1 + direction.1 * half);
+        let child_direction = (direction.1, direction.0);
+        let child_length = length * T::from(SCALE).unwrap();
+
+        segments.push((center, first));
+        self.visit_branch(segments, first, child_direction, child_length, order + 1);
+        segments.push((first, second));
+        self.visit_branch(segments, second, child_direction, child_length, order + 1);
+        segments.push((second, center));
+    }
+}
+
+impl<T> IntoIterator for HTreeCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // root segment spans the middle half of the unit width
+        let half = T::from(0.25).unwrap();
+        let center = (T::from(0.5).unwrap(), T::from(0.5 * SCALE).unwrap());
+        let first = (center.0 - half, center.1);
+        let second = (center.0 + half, center.1);
+        let child_direction = (T::from(0).unwrap(), T::from(1).unwrap());
+        let child_length = T::from(0.5 * SCALE).unwrap();
+
+        let mut segments = Vec::new();
+        self.visit_branch(&mut segments, first, child_direction, child_length, 1);
+        segments.push((first, second));
+        self.visit_branch(&mut segments, second, child_direction, child_length, 1);
+        segments.into()
+    }
+}
+
+impl<T> BaseCurve<T> for HTreeCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+}
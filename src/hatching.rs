@@ -0,0 +1,85 @@
+use image::{ImageBuffer, Luma};
+use num::{traits::Euclid, Float};
+
+use crate::{
+    approximate,
+    line_utils::{partition_line, Line},
+    scanline_curve::ScanlineCurve,
+};
+
+/// Parallel hatch lines drawn where the image is darker than the threshold
+#[derive(Clone, Copy, Debug)]
+pub struct HatchLayer<T> {
+    /// Angle of the lines to the x axis in radians
+    pub angle: T,
+    /// Brightness (0-255) below which the lines are drawn
+    pub threshold: T,
+}
+
+impl<T> HatchLayer<T>
+where
+    T: Float,
+{
+    pub fn new(angle: T, threshold: T) -> HatchLayer<T> {
+        HatchLayer { angle, threshold }
+    }
+
+    /// Returns up to four layers at 0°, 45°, 90° and 135°, each one drawn in darker regions than the previous one.
+    /// The thresholds split the brightness range evenly.
+    pub fn layers(num: usize) -> Vec<HatchLayer<T>> {
+        let num = num.clamp(1, 4);
+        (0..num)
+            .map(|i| {
+                HatchLayer::new(
+                    T::from(i as f64 * std::f64::consts::FRAC_PI_4).unwrap(),
+                    T::from(255 * (num - i)).unwrap() / T::from(num + 1).unwrap(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Function cross-hatches the image with one set of lines per layer, `spacing` apart as fraction of the larger image side.
+///
+/// The lines are split into pieces of one pixel, a piece is kept if the image around it is darker than the threshold
+/// of its layer and adjacent pieces are merged again. One segment list is returned per layer, the lines of a layer
+/// alternate in direction to keep pen moves short.
+pub fn hatch<T>(
+    image: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    layers: &[HatchLayer<T>],
+    spacing: T,
+) -> Vec<Vec<Line<T>>>
+where
+    T: Float + Euclid + std::iter::Sum,
+{
+    let (width, height) = (
+        T::from(image.width()).unwrap(),
+        T::from(image.height()).unwrap(),
+    );
+    let white = T::from(255).unwrap();
+    layers
+        .iter()
+        .map(|layer| {
+            let mut lines: Vec<Line<T>> = Vec::new();
+            for (start, stop) in ScanlineCurve::new(spacing, layer.angle).rows(width, height) {
+                // consecutive visible pieces of the row are merged into one segment
+                let mut visible: Option<Line<T>> = None;
+                for piece in partition_line(&start, &stop, T::one()) {
+                    let brightness =
+                        white - approximate(image, &piece.0, &piece.1, T::one()) * white;
+                    visible = match (visible, brightness < layer.threshold) {
+                        (Some(line), true) => Some((line.0, piece.1)),
+                        (None, true) => Some(piece),
+                        (Some(line), false) => {
+                            lines.push(line);
+                            None
+                        }
+                        (None, false) => None,
+                    };
+                }
+                lines.extend(visible);
+            }
+            lines
+        })
+        .collect()
+}
@@ -1,8 +1,10 @@
 use hilbert_index::FromHilbertIndex;
 
-use num::Float;
+use num::{traits::Euclid, Float};
 use std::marker::PhantomData;
 
+use crate::base_curve::BaseCurve;
+
 
 #[derive(Clone, Copy, Debug)]
 pub struct HilbertCurve<T> {
@@ -70,3 +72,12 @@ where
         }
     }
 }
+
+impl<T> BaseCurve<T> for HilbertCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+}
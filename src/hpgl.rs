@@ -0,0 +1,120 @@
+use std::{fmt::Write, fs, io, path::Path};
+
+use num::{traits::Euclid, Float};
+
+use crate::line_utils::{join_lines, Line};
+
+/// Plotter units per mm
+pub const UNITS_PER_MM: f64 = 40.0;
+
+/// Paper sizes in landscape orientation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PageSize {
+    A4,
+    A3,
+    Letter,
+    Tabloid,
+    /// width and height in mm
+    Custom(f64, f64),
+}
+
+impl PageSize {
+    /// Returns width and height of the page in mm
+    pub fn dimensions_mm(&self) -> (f64, f64) {
+        match self {
+            PageSize::A4 => (297.0, 210.0),
+            PageSize::A3 => (420.0, 297.0),
+            PageSize::Letter => (279.4, 215.9),
+            PageSize::Tabloid => (431.8, 279.4),
+            PageSize::Custom(width, height) => (*width, *height),
+        }
+    }
+}
+
+/// Options of the generated hpgl program
+#[derive(Clone, Copy, Debug)]
+pub struct HpglOptions {
+    pub page_size: PageSize,
+    /// space kept free at each side of the page in mm
+    pub margin_mm: f64,
+    /// number of the pen selected with SP
+    pub pen: u8,
+    /// maximal distance between the end of a line and the start of the next one to draw them without lifting the pen
+    pub join_tolerance: f64,
+}
+
+impl HpglOptions {
+    /// Returns options drawing with the given pen on a page of given size
+    pub fn new(page_size: PageSize, pen: u8) -> HpglOptions {
+        HpglOptions {
+            page_size,
+            margin_mm: 10.0,
+            pen,
+            join_tolerance: 1e-3,
+        }
+    }
+}
+
+/// Function writes lines as hpgl program, the image of given width and height is scaled to fit the page
+pub fn to_hpgl<T>(lines: &[Line<T>], width: u32, height: u32, options: &HpglOptions) -> String
+where
+    T: Float + Euclid,
+{
+    let (page_width, page_height) = options.page_size.dimensions_mm();
+    let drawing_width = page_width - 2.0 * options.margin_mm;
+    let drawing_height = page_height - 2.0 * options.margin_mm;
+    // plotter units per image pixel, the image keeps its aspect ratio and is centered on the page
+    let scale = (drawing_width / width as f64).min(drawing_height / height as f64) * UNITS_PER_MM;
+    let offset = (
+        (page_width * UNITS_PER_MM - width as f64 * scale) / 2.0,
+        (page_height * UNITS_PER_MM - height as f64 * scale) / 2.0,
+    );
+    // hpgl y axis points up -> flip image vertically
+    let plotter_position = |point: &(T, T)| {
+        (
+            (offset.0 + point.0.to_f64().unwrap() * scale).round() as i64,
+            (offset.1 + (height as f64 - point.1.to_f64().unwrap()) * scale).round() as i64,
+        )
+    };
+
+    let mut hpgl = String::new();
+    writeln!(hpgl, "IN;").unwrap();
+    writeln!(hpgl, "SP{};", options.pen).unwrap();
+    writeln!(hpgl, "PA;").unwrap();
+    for polyline in join_lines(lines, T::from(options.join_tolerance).unwrap()) {
+        let mut points: Vec<(i64, i64)> = polyline.iter().map(plotter_position).collect();
+        points.dedup();
+        let (x, y) = points[0];
+        writeln!(hpgl, "PU{},{};", x, y).unwrap();
+        let coordinates = points
+            .iter()
+            .skip(1)
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect::<Vec<String>>()
+            .join(",");
+        // a polyline collapsing to a single plotter position is drawn as dot
+        if coordinates.is_empty() {
+            writeln!(hpgl, "PD;").unwrap();
+        } else {
+            writeln!(hpgl, "PD{};", coordinates).unwrap();
+        }
+    }
+    writeln!(hpgl, "PU;").unwrap();
+    writeln!(hpgl, "SP0;").unwrap();
+    hpgl
+}
+
+/// Function saves lines as hpgl program, see [to_hpgl]
+pub fn save_hpgl<T, P>(
+    path: P,
+    lines: &[Line<T>],
+    width: u32,
+    height: u32,
+    options: &HpglOptions,
+) -> io::Result<()>
+where
+    T: Float + Euclid,
+    P: AsRef<Path>,
+{
+    fs::write(path, to_hpgl(lines, width, height, options))
+}
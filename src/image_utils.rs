@@ -25,24 +25,24 @@ where
             .0[0],
     )
     .unwrap()
-        * (x_factor)
-        * (y_factor);
+        * (x_factor_opp)
+        * (y_factor_opp);
     let dx = T::from(
         image
             .get_pixel((x + 1).min(width - 1).max(0), (y).min(height - 1).max(0))
             .0[0],
     )
     .unwrap()
-        * (x_factor_opp)
-        * (y_factor);
+        * (x_factor)
+        * (y_factor_opp);
     let dy = T::from(
         image
             .get_pixel((x).min(width - 1).max(0), (y + 1).min(height - 1).max(0))
             .0[0],
     )
     .unwrap()
-        * (x_factor)
-        * (y_factor_opp);
+        * (x_factor_opp)
+        * (y_factor);
     let dx_dy = T::from(
         image
             .get_pixel(
@@ -52,8 +52,8 @@ where
             .0[0],
     )
     .unwrap()
-        * (x_factor_opp)
-        * (y_factor_opp);
+        * (x_factor)
+        * (y_factor);
 
     on_site + dx + dy + dx_dy
 }
@@ -0,0 +1,162 @@
+use image::{ImageBuffer, Luma};
+use num::{traits::Euclid, Float};
+use std::collections::HashMap;
+
+use crate::{
+    image_utils::get_brightness,
+    line_utils::{connect_points, Line},
+};
+
+/// Edge of the sampling grid, `(i, j, 0)` runs from node `(i, j)` to the right and `(i, j, 1)` downwards
+type Edge = (usize, usize, u8);
+
+/// Function returns `num` brightness levels spread over the brightness range of the image.
+///
+/// The levels are evenly spaced for `gamma == 1`, larger values of `gamma` space the darker levels more densely.
+pub fn iso_levels<T>(image: &ImageBuffer<Luma<u8>, Vec<u8>>, num: usize, gamma: T) -> Vec<T>
+where
+    T: Float,
+{
+    let min = T::from(*image.iter().min().unwrap()).unwrap();
+    let max = T::from(*image.iter().max().unwrap()).unwrap();
+    (1..=num)
+        .map(|i| T::from(i).unwrap() / T::from(num + 1).unwrap())
+        .map(|fraction| min + (max - min) * fraction.powf(gamma))
+        .collect()
+}
+
+/// Function extracts the iso-lines of the image brightness at the given levels with marching squares,
+/// see <https://en.wikipedia.org/wiki/Marching_squares>
+///
+/// The brightness is sampled every `step` pixels. One segment list is returned per level,
+/// the segments are ordered such that each contour is traced continuously.
+pub fn iso_contours<T>(
+    image: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    levels: &[T],
+    step: T,
+) -> Vec<Vec<Line<T>>>
+where
+    T: Float + Euclid,
+{
+    let max_x = T::from(image.width() - 1).unwrap();
+    let max_y = T::from(image.height() - 1).unwrap();
+    let num_x = (max_x / step).floor().to_usize().unwrap() + 1;
+    let num_y = (max_y / step).floor().to_usize().unwrap() + 1;
+    let position = |i: usize, j: usize| (T::from(i).unwrap() * step, T::from(j).unwrap() * step);
+    let samples: Vec<T> = (0..num_y)
+        .flat_map(|j| (0..num_x).map(move |i| (i, j)))
+        .map(|(i, j)| {
+            let (x, y) = position(i, j);
+            get_brightness(image, x, y)
+        })
+        .collect();
+    let sample = |i: usize, j: usize| samples[j * num_x + i];
+
+    levels
+        .iter()
+        .map(|level| {
+            // point where the contour crosses an edge, interpolated linearly between its nodes
+            let crossing = |(i, j, direction): Edge| {
+                let (i_next, j_next) = if direction == 0 {
+                    (i + 1, j)
+                } else {
+                    (i, j + 1)
+                };
+                let (from, to) = (sample(i, j), sample(i_next, j_next));
+                let t = (*level - from) / (to - from);
+                let (start, stop) = (position(i, j), position(i_next, j_next));
+                (
+                    start.0 + t * (stop.0 - start.0),
+                    start.1 + t * (stop.1 - start.1),
+                )
+            };
+            let mut pieces = Vec::new();
+            for j in 0..num_y - 1 {
+                for i in 0..num_x - 1 {
+                    pieces.extend(cell_pieces(i, j, &sample, *level));
+                }
+            }
+            trace(&pieces)
+                .iter()
+                .flat_map(|edges| {
+                    connect_points(&edges.iter().map(|edge| crossing(*edge)).collect::<Vec<_>>())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Function returns the pieces of contour within the cell with upper left node `(i, j)` as pairs of crossed edges
+fn cell_pieces<T, F>(i: usize, j: usize, sample: &F, level: T) -> Vec<(Edge, Edge)>
+where
+    T: Float,
+    F: Fn(usize, usize) -> T,
+{
+    // corners clockwise from the upper left, each followed by the edge to the next corner
+    let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+    let edges: [Edge; 4] = [(i, j, 0), (i + 1, j, 1), (i, j + 1, 0), (i, j, 1)];
+    let above: Vec<bool> = corners
+        .iter()
+        .map(|(i, j)| sample(*i, *j) > level)
+        .collect();
+    let crossed: Vec<Edge> = (0..4)
+        .filter(|k| above[*k] != above[(k + 1) % 4])
+        .map(|k| edges[k])
+        .collect();
+    match crossed.len() {
+        2 => vec![(crossed[0], crossed[1])],
+        4 => {
+            // saddle, the mean of the corners decides which opposite corners are connected
+            let center = corners
+                .iter()
+                .fold(T::zero(), |sum, (i, j)| sum + sample(*i, *j))
+                / T::from(4).unwrap();
+            if (center > level) == above[0] {
+                // cut off the upper right and lower left corners
+                vec![(edges[0], edges[1]), (edges[2], edges[3])]
+            } else {
+                // cut off the upper left and lower right corners
+                vec![(edges[3], edges[0]), (edges[1], edges[2])]
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Function chains pieces sharing an edge into contours, returned as sequences of crossed edges
+fn trace(pieces: &[(Edge, Edge)]) -> Vec<Vec<Edge>> {
+    let mut neighbours: HashMap<Edge, Vec<usize>> = HashMap::new();
+    for (index, (start, stop)) in pieces.iter().enumerate() {
+        neighbours.entry(*start).or_default().push(index);
+        neighbours.entry(*stop).or_default().push(index);
+    }
+    let mut used = vec![false; pieces.len()];
+    // follows unused pieces from `edge` on, returning the edges passed
+    let follow = |mut edge: Edge, used: &mut Vec<bool>| {
+        let mut edges = Vec::new();
+        while let Some(index) = neighbours[&edge].iter().find(|index| !used[**index]) {
+            used[*index] = true;
+            let (start, stop) = pieces[*index];
+            edge = if start == edge { stop } else { start };
+            edges.push(edge);
+        }
+        edges
+    };
+
+    let mut contours = Vec::new();
+    for index in 0..pieces.len() {
+        if used[index] {
+            continue;
+        }
+        used[index] = true;
+        let (start, stop) = pieces[index];
+        let forward = follow(stop, &mut used);
+        let backward = follow(start, &mut used);
+        let mut contour: Vec<Edge> = backward.into_iter().rev().collect();
+        contour.push(start);
+        contour.push(stop);
+        contour.extend(forward);
+        contours.push(contour);
+    }
+    contours
+}
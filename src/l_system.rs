@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+/// Function applies the production rules `order` times to the axiom, symbols without rule are kept
+pub fn expand(axiom: &str, rules: &HashMap<char, &str>, order: usize) -> String {
+    let mut commands = axiom.to_string();
+    for _ in 0..order {
+        commands = commands
+            .chars()
+            .map(|symbol| match rules.get(&symbol) {
+                Some(replacement) => replacement.to_string(),
+                None => symbol.to_string(),
+            })
+            .collect();
+    }
+    commands
+}
+
+/// Function interprets commands as turtle graphics and returns the visited points.
+///
+/// Symbols in `draw` move the turtle one unit forward, `+` turns left and `-` turns right by `angle` degrees,
+/// all other symbols are ignored.
+pub fn turtle(commands: &str, angle: f64, draw: &[char]) -> Vec<(f64, f64)> {
+    let mut points = vec![(0f64, 0f64)];
+    let mut heading = 0f64;
+    for symbol in commands.chars() {
+        match symbol {
+            '+' => heading += angle,
+            '-' => heading -= angle,
+            symbol if draw.contains(&symbol) => {
+                let (x, y) = *points.last().unwrap();
+                let radians = heading.to_radians();
+                points.push((x + radians.cos(), y + radians.sin()));
+            }
+            _ => {}
+        }
+    }
+    points
+}
+
+/// Function scales points such that their bounding box is centered in the unit square with its longer side spanning it
+pub fn to_unit_square(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let min_x = points.iter().map(|point| point.0).fold(f64::INFINITY, f64::min);
+    let max_x = points.iter().map(|point| point.0).fold(f64::NEG_INFINITY, f64::max);
+    let min_y = points.iter().map(|point| point.1).fold(f64::INFINITY, f64::min);
+    let max_y = points.iter().map(|point| point.1).fold(f64::NEG_INFINITY, f64::max);
+    let extent = (max_x - min_x).max(max_y - min_y).max(f64::EPSILON);
+    let offset = (
+        (1.0 - (max_x - min_x) / extent) / 2.0,
+        (1.0 - (max_y - min_y) / extent) / 2.0,
+    );
+    points
+        .iter()
+        .map(|(x, y)| ((x - min_x) / extent + offset.0, (y - min_y) / extent + offset.1))
+        .collect()
+}
@@ -1,8 +1,31 @@
 
 pub mod line_utils;
+pub mod base_curve;
 mod image_utils;
+mod l_system;
 pub mod hilbert_curve;
+pub mod h_tree_curve;
+pub mod peano_curve;
+pub mod moore_curve;
+pub mod gosper_curve;
+pub mod generalized_hilbert_curve;
+pub mod adaptive_hilbert_curve;
+pub mod spiral_curve;
+pub mod scanline_curve;
+pub mod concentric_curve;
+pub mod sierpinski_curve;
+pub mod z_order_curve;
+pub mod parametric_curve;
+pub mod streamline_curve;
+pub mod iso_contour;
+pub mod stipple;
+pub mod tsp_curve;
+pub mod hatching;
+pub mod modulation;
 pub mod approximator;
+pub mod svg;
+pub mod gcode;
+pub mod hpgl;
 use image::{ImageBuffer, Luma};
 use itertools::iproduct;
 use crate::line_utils::{length,partition_line};
@@ -5,6 +5,38 @@ use itertools::iproduct;
 use num::{traits::Euclid, Float, Num};
 use num_traits::NumCast;
 
+/// Line segment from its start to its stop point
+pub type Line<T> = ((T, T), (T, T));
+
+/// Iterator over the precomputed lines of a path
+pub struct LineIterator<T> {
+    lines: std::vec::IntoIter<Line<T>>,
+}
+
+impl<T> From<Vec<Line<T>>> for LineIterator<T> {
+    fn from(lines: Vec<Line<T>>) -> LineIterator<T> {
+        LineIterator {
+            lines: lines.into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for LineIterator<T> {
+    type Item = Line<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.next()
+    }
+}
+
+/// Function connects consecutive points of a polyline by lines
+pub fn connect_points<T>(points: &[(T, T)]) -> Vec<Line<T>>
+where
+    T: Copy,
+{
+    points.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
 pub trait Length<T>
 where
     T: Float + Euclid + std::iter::Sum,
@@ -30,6 +62,9 @@ pub fn set_scale<T>(lines: &mut Vec<((T, T), (T, T))>, width: usize, height: usi
 where
     T: Float + Euclid,
 {
+    if lines.is_empty() {
+        return;
+    }
     let min_x = lines
         .iter()
         .map(|(start, stop)| vec![start.0, stop.0])
@@ -57,11 +92,19 @@ where
 
     let width = T::from(width).unwrap();
     let height = T::from(height).unwrap();
+    // lines without extent along an axis are centered on it
+    let scale = |value: T, min: T, max: T, size: T| {
+        if max > min {
+            (value - min) * size / (max - min)
+        } else {
+            size / T::from(2).unwrap()
+        }
+    };
     for (start, stop) in lines.iter_mut() {
-        start.0 = (start.0 - min_x) * width / (max_x - min_x);
-        stop.0 = (stop.0 - min_x) * width / (max_x - min_x);
-        start.1 = (start.1 - min_y) * height / (max_y - min_y);
-        stop.1 = (stop.1 - min_y) * height / (max_y - min_y);
+        start.0 = scale(start.0, min_x, max_x, width);
+        stop.0 = scale(stop.0, min_x, max_x, width);
+        start.1 = scale(start.1, min_y, max_y, height);
+        stop.1 = scale(stop.1, min_y, max_y, height);
     }
 }
 
@@ -89,6 +132,79 @@ where
     });
 }
 
+/// Function scales lines given in unit-square coordinates to a square covering the image, keeping their aspect ratio.
+/// The square is centered on the image and lines reaching outside of the image are dropped
+pub fn cover_image<T>(lines: &mut Vec<Line<T>>, width: usize, height: usize)
+where
+    T: Float + Euclid,
+{
+    let side = T::from(width.max(height)).unwrap();
+    let two = T::from(2).unwrap();
+    let offset = (
+        (side - T::from(width).unwrap()) / two,
+        (side - T::from(height).unwrap()) / two,
+    );
+    for (start, stop) in lines.iter_mut() {
+        *start = (start.0 * side - offset.0, start.1 * side - offset.1);
+        *stop = (stop.0 * side - offset.0, stop.1 * side - offset.1);
+    }
+    crop(lines, width, height);
+}
+
+/// Function drops lines reaching outside of an image of given size
+pub fn crop<T>(lines: &mut Vec<Line<T>>, width: usize, height: usize)
+where
+    T: Float + Euclid,
+{
+    let width = T::from(width).unwrap();
+    let height = T::from(height).unwrap();
+    let inside = |point: &(T, T)| {
+        point.0 >= T::zero() && point.0 <= width && point.1 >= T::zero() && point.1 <= height
+    };
+    lines.retain(|(start, stop)| inside(start) && inside(stop));
+}
+
+/// Function cuts lines at the border of an image of given size, lines outside of the image are dropped
+pub fn clip<T>(lines: &mut Vec<Line<T>>, width: usize, height: usize)
+where
+    T: Float + Euclid,
+{
+    let width = T::from(width).unwrap();
+    let height = T::from(height).unwrap();
+    *lines = lines
+        .iter()
+        .filter_map(|(start, stop)| {
+            let delta = (stop.0 - start.0, stop.1 - start.1);
+            // part of the line within the image, as fractions of the line
+            let (mut first, mut last) = (T::zero(), T::one());
+            for (direction, distance) in [
+                (-delta.0, start.0),
+                (delta.0, width - start.0),
+                (-delta.1, start.1),
+                (delta.1, height - start.1),
+            ] {
+                if direction == T::zero() {
+                    if distance < T::zero() {
+                        return None;
+                    }
+                } else if direction < T::zero() {
+                    first = first.max(distance / direction);
+                } else {
+                    last = last.min(distance / direction);
+                }
+            }
+            if first > last {
+                return None;
+            }
+            let at = |t: T| (start.0 + t * delta.0, start.1 + t * delta.1);
+            Some((
+                if first > T::zero() { at(first) } else { *start },
+                if last < T::one() { at(last) } else { *stop },
+            ))
+        })
+        .collect();
+}
+
 /// Function emulates a line with thickness with multiple lines with thickness 1
 pub fn thicken_line_sin<T>(
     start: &(T, T),
@@ -157,26 +273,31 @@ where
     angle
 }
 
+/// Function rounds the corners between connected lines with quadratic bezier curves through the midpoints of the lines,
+/// lines not connected to the next one are kept as they are
 pub fn smooth_corners<T>(lines: &Vec<((T, T), (T, T))>)->Vec<((T, T), (T, T))>
 where
     T: Float + Euclid + std::ops::AddAssign + std::iter::Sum<T>,
 {
+    if lines.is_empty(){
+        return Vec::new();
+    }
     let mut lines=lines.clone();
     let mut resulting_lines:Vec<((T, T),(T, T))>=Vec::new();
-    let mut hit=false;
     let one=T::from(1).unwrap();
     let two=T::from(2).unwrap();
     for i in 0..lines.len() - 1 {
         let line1 = lines[i];
         let line2 = lines[i + 1];
         if line1.1 != line2.0 {
+            // keep the end of a polyline when the path consists of several
+            resulting_lines.push(line1);
             continue;
         }
         let direction1=get_direction(&line1);
         let direction2=get_direction(&line2);
         
         if direction1.0!=direction2.0 ||direction1.1!=direction2.1{
-            hit=true;
             let control_point=line1.1;
             let start:(T,T)=(
                 (line1.0.0+line1.1.0)/two,
@@ -204,13 +325,11 @@ where
             lines[i+1]=(stop,line2.1);
 
         }else{
-            hit=false;
             resulting_lines.push(line1);
         }
     }
-    if !hit{
-        resulting_lines.push(*lines.iter().last().unwrap());
-    }
+    // the last line is shortened if it starts at a corner
+    resulting_lines.push(*lines.iter().last().unwrap());
     resulting_lines
 }
 /// Function emulates a line with thickness with multiple lines with thickness 1
@@ -264,6 +383,110 @@ where
         .collect()
 }
 
+/// Function emulates lines with thickness with parallel copies of the lines, `spacing` apart.
+///
+/// Each line gets its thickness divided by the spacing copies, at least one. The copies alternate between both sides
+/// of the path, so copy `i` keeps its distance to the path and runs as one polyline along all connected lines with
+/// more than `i` copies. Copies only start or stop where the number of copies changes, they meet at mitered vertices
+/// and their corners are rounded by [smooth_corners].
+/// Every other copy runs backwards to keep the moves between copies short.
+/// With `drop_bright` lines thinner than one pixel are left out.
+pub fn thicken_lines_parallel<T>(
+    lines: &[Line<T>],
+    thicknesses: &[T],
+    spacing: T,
+    drop_bright: bool,
+) -> Vec<Line<T>>
+where
+    T: Float + Euclid + std::ops::AddAssign + std::iter::Sum<T>,
+{
+    let half = T::from(0.5).unwrap();
+    // lines whose ends are closer than this are offset together
+    let tolerance = T::from(1e-3).unwrap();
+    // polylines of connected lines with the number of copies drawn along each of their lines
+    let mut paths: Vec<(Vec<_>, Vec<usize>)> = Vec::new();
+    for ((start, stop), thickness) in lines.iter().zip(thicknesses) {
+        if (drop_bright && *thickness < T::one()) || start == stop {
+            continue;
+        }
+        let copies = (*thickness / spacing)
+            .round()
+            .to_usize()
+            .unwrap_or(0)
+            .max(1);
+        match paths.last_mut() {
+            Some((points, counts)) if length(points.last().unwrap(), start) < tolerance => {
+                points.push(*stop);
+                counts.push(copies);
+            }
+            _ => paths.push((vec![*start, *stop], vec![copies])),
+        }
+    }
+
+    paths
+        .iter()
+        .flat_map(|(points, counts)| {
+            let normals: Vec<(T, T)> = points
+                .windows(2)
+                .map(|pair| {
+                    let length = length(&pair[0], &pair[1]);
+                    (
+                        (pair[0].1 - pair[1].1) / length,
+                        (pair[1].0 - pair[0].0) / length,
+                    )
+                })
+                .collect();
+            // offset per unit distance, at a vertex the miter keeps both adjacent lines at the distance,
+            // at sharp turns it is limited to twice the distance
+            let offsets: Vec<(T, T)> = (0..points.len())
+                .map(|i| {
+                    let before = normals[i.saturating_sub(1)];
+                    let after = normals[i.min(normals.len() - 1)];
+                    let scale = (T::one() + before.0 * after.0 + before.1 * after.1).max(half);
+                    ((before.0 + after.0) / scale, (before.1 + after.1) / scale)
+                })
+                .collect();
+            let max_copies = counts.iter().copied().max().unwrap_or(0);
+            (0..max_copies).flat_map(move |i| {
+                // copy 0 follows the path, the following ones alternate between its sides
+                let side = if i % 2 == 0 { -T::one() } else { T::one() };
+                let distance = T::from(i.div_ceil(2)).unwrap() * spacing * side;
+                // lines `first..last` of the path form a run of the copy
+                let mut runs: Vec<Vec<Line<T>>> = Vec::new();
+                let mut first = None;
+                for j in 0..=counts.len() {
+                    match (first, counts.get(j).is_some_and(|count| *count > i)) {
+                        (None, true) => first = Some(j),
+                        (Some(start), false) => {
+                            let copy: Vec<(T, T)> = points[start..=j]
+                                .iter()
+                                .zip(&offsets[start..=j])
+                                .map(|(point, offset)| {
+                                    (point.0 + offset.0 * distance, point.1 + offset.1 * distance)
+                                })
+                                .collect();
+                            runs.push(smooth_corners(&connect_points(&copy)));
+                            first = None;
+                        }
+                        _ => {}
+                    }
+                }
+                if i % 2 == 1 {
+                    runs.reverse();
+                    for run in runs.iter_mut() {
+                        *run = run
+                            .iter()
+                            .rev()
+                            .map(|(start, stop)| (*stop, *start))
+                            .collect();
+                    }
+                }
+                runs.into_iter().flatten()
+            })
+        })
+        .collect()
+}
+
 /// Function emulates a line with thickness with multiple lines with thickness 1
 pub fn thicken_line<T>(start: &(T, T), stop: &(T, T), thickness: T) -> Vec<((T, T), (T, T))>
 where
@@ -374,3 +597,20 @@ where
     ((start.0 - stop.0).powf(T::from(2).unwrap()) + (start.1 - stop.1).powf(T::from(2).unwrap()))
         .sqrt()
 }
+
+/// Function joins consecutive lines whose endpoints coincide (within tolerance) into polylines
+pub fn join_lines<T>(lines: &[Line<T>], tolerance: T) -> Vec<Vec<(T, T)>>
+where
+    T: Float + Euclid,
+{
+    let mut polylines: Vec<Vec<(T, T)>> = Vec::new();
+    for (start, stop) in lines {
+        match polylines.last_mut() {
+            Some(polyline) if length(polyline.last().unwrap(), start) <= tolerance => {
+                polyline.push(*stop)
+            }
+            _ => polylines.push(vec![*start, *stop]),
+        }
+    }
+    polylines
+}
@@ -0,0 +1,384 @@
+use num::{traits::Euclid, Float};
+
+use crate::line_utils::{connect_points, length, Line};
+
+/// Periodic waveform offsetting a line sideways
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Waveform {
+    Sine,
+    /// Straight flanks with sharp corners
+    Triangle,
+    /// Meander, the pen jumps between both sides of the line
+    Square,
+    /// Rising flank followed by a jump back, ignores the duty cycle
+    Sawtooth,
+}
+
+/// Waveform with angular frequency `omega` per pixel along the path.
+///
+/// The duty cycle is the fraction of each period spent on the positive side of the line.
+#[derive(Clone, Copy, Debug)]
+pub struct Modulation<T> {
+    pub waveform: Waveform,
+    pub omega: T,
+    pub duty_cycle: T,
+}
+
+impl<T> Modulation<T>
+where
+    T: Float + Euclid,
+{
+    /// Returns the modulation with a duty cycle of one half
+    pub fn new(waveform: Waveform, omega: T) -> Modulation<T> {
+        Modulation {
+            waveform,
+            omega,
+            duty_cycle: T::from(0.5).unwrap(),
+        }
+    }
+
+    /// Returns the modulation with given duty cycle, clamped to `[0.01, 0.99]`
+    pub fn with_duty_cycle(mut self, duty_cycle: T) -> Modulation<T> {
+        self.duty_cycle = duty_cycle
+            .max(T::from(0.01).unwrap())
+            .min(T::from(0.99).unwrap());
+        self
+    }
+
+    /// Returns the length of one period in pixels
+    pub fn period(&self) -> T {
+        T::from(2f64 * std::f64::consts::PI).unwrap() / self.omega
+    }
+
+    /// Returns the value in `[-1, 1]` at `phase` in `[0, 1)`, counted in periods.
+    /// At a jump `before` selects the value reached from lower phases.
+    pub fn value(&self, phase: T, before: bool) -> T {
+        let one = T::one();
+        let two = T::from(2).unwrap();
+        let pi = T::from(std::f64::consts::PI).unwrap();
+        let duty = self.duty_cycle;
+        // with `before` the intervals are closed at their end, so a jump at the start of the period belongs to the end
+        let phase = if before && phase == T::zero() {
+            one
+        } else {
+            phase
+        };
+        let first_half = if before { phase <= duty } else { phase < duty };
+        match self.waveform {
+            Waveform::Sine if first_half => (pi * phase / duty).sin(),
+            Waveform::Sine => -(pi * (phase - duty) / (one - duty)).sin(),
+            Waveform::Triangle if first_half => two * phase / duty - one,
+            Waveform::Triangle => one - two * (phase - duty) / (one - duty),
+            Waveform::Square if first_half => one,
+            Waveform::Square => -one,
+            Waveform::Sawtooth => two * phase - one,
+        }
+    }
+
+    /// Returns the phases within a period at which the waveform has a corner or jumps
+    fn breakpoints(&self) -> Vec<T> {
+        match self.waveform {
+            Waveform::Sine => Vec::new(),
+            Waveform::Triangle | Waveform::Square => vec![T::zero(), self.duty_cycle],
+            Waveform::Sawtooth => vec![T::zero()],
+        }
+    }
+}
+
+/// Function emulates lines with thickness by a single stroke oscillating around them with the given waveform.
+///
+/// Like [thicken_lines_sin](crate::line_utils::thicken_lines_sin) the stroke swings out by the thickness of each line to
+/// both sides, it stays connected along connected lines. Its phase runs on along
+/// the whole path, corners and jumps of the waveform are placed exactly.
+/// With `drop_bright` lines thinner than one pixel are left out.
+pub fn modulate_lines<T>(
+    lines: &[Line<T>],
+    thicknesses: &[T],
+    modulation: &Modulation<T>,
+    drop_bright: bool,
+) -> Vec<Line<T>>
+where
+    T: Float + Euclid,
+{
+    oscillate(
+        lines,
+        &amplitudes(thicknesses, T::one(), modulation.omega, drop_bright),
+        &modulation.breakpoints(),
+        false,
+        |phase, before| (T::zero(), modulation.value(phase, before)),
+    )
+}
+
+/// Function emulates lines with thickness like [modulate_lines], but the stroke runs smoothly through corners.
+///
+/// Instead of offsetting perpendicular to each line, the offset direction and the amplitude are averaged at the
+/// vertices between connected lines and interpolated along the lines. So the stroke has no spikes where the path
+/// turns or the thickness changes.
+pub fn smooth_modulate_lines<T>(
+    lines: &[Line<T>],
+    thicknesses: &[T],
+    modulation: &Modulation<T>,
+    drop_bright: bool,
+) -> Vec<Line<T>>
+where
+    T: Float + Euclid,
+{
+    oscillate(
+        lines,
+        &amplitudes(thicknesses, T::one(), modulation.omega, drop_bright),
+        &modulation.breakpoints(),
+        true,
+        |phase, before| (T::zero(), modulation.value(phase, before)),
+    )
+}
+
+/// Function emulates lines with thickness by a stroke of constant amplitude whose frequency rises with the thickness.
+///
+/// The stroke spans `max_thickness`, the angular frequency grows linearly from zero for lines without thickness to
+/// `modulation.omega` for lines of `max_thickness`. The phase is integrated along the path, so it stays continuous
+/// where the frequency changes. With `drop_bright` lines thinner than one pixel are left out.
+pub fn frequency_modulate_lines<T>(
+    lines: &[Line<T>],
+    thicknesses: &[T],
+    max_thickness: T,
+    modulation: &Modulation<T>,
+    drop_bright: bool,
+) -> Vec<Line<T>>
+where
+    T: Float + Euclid,
+{
+    let amplitude = max_thickness * T::from(0.5).unwrap();
+    let oscillations: Vec<(Option<T>, T)> = thicknesses
+        .iter()
+        .map(|thickness| {
+            let darkness = (*thickness / max_thickness).max(T::zero()).min(T::one());
+            let amplitude = if drop_bright && *thickness < T::one() {
+                None
+            } else {
+                Some(amplitude)
+            };
+            (amplitude, modulation.omega * darkness)
+        })
+        .collect();
+    oscillate(
+        lines,
+        &oscillations,
+        &modulation.breakpoints(),
+        false,
+        |phase, before| (T::zero(), modulation.value(phase, before)),
+    )
+}
+
+/// Function emulates lines with thickness by a single stroke drawing loops around them, like a scribble.
+///
+/// The pen circles around a center moving along the lines, the radius of the loops is half the thickness of each line
+/// and `omega` is the angular frequency of the circling per pixel along the path. Loops only close on themselves
+/// where the radius exceeds `1 / omega`, thinner lines give a wavy stroke. The stroke stays connected along connected
+/// lines. With `drop_bright` lines thinner than one pixel are left out.
+pub fn loop_lines<T>(
+    lines: &[Line<T>],
+    thicknesses: &[T],
+    omega: T,
+    drop_bright: bool,
+) -> Vec<Line<T>>
+where
+    T: Float + Euclid,
+{
+    let two_pi = T::from(2f64 * std::f64::consts::PI).unwrap();
+    oscillate(
+        lines,
+        &amplitudes(thicknesses, T::from(0.5).unwrap(), omega, drop_bright),
+        &[],
+        false,
+        |phase, _| ((two_pi * phase).cos(), (two_pi * phase).sin()),
+    )
+}
+
+/// Function returns the thickness times `scale` as amplitude and `omega` for each line, with `drop_bright` no amplitude
+/// for lines thinner than one pixel
+fn amplitudes<T>(thicknesses: &[T], scale: T, omega: T, drop_bright: bool) -> Vec<(Option<T>, T)>
+where
+    T: Float,
+{
+    thicknesses
+        .iter()
+        .map(|thickness| {
+            if drop_bright && *thickness < T::one() {
+                (None, omega)
+            } else {
+                (Some(*thickness * scale), omega)
+            }
+        })
+        .collect()
+}
+
+/// Function draws a stroke oscillating around the lines.
+///
+/// Each line comes with the amplitude and angular frequency of the oscillation along it, lines without amplitude are
+/// left out. The phase is the integral of the frequency along the whole path, it runs on along left out lines. `offset` returns the offset of the pen along and across
+/// the line in units of the amplitude for a phase in periods, at the `breakpoints` it may jump and `before` selects
+/// the offset reached from lower phases. With `smooth` the normals and amplitudes of connected lines are averaged at
+/// their common vertex and interpolated along the lines, otherwise each line is offset along its own normal.
+fn oscillate<T, F>(
+    lines: &[Line<T>],
+    oscillations: &[(Option<T>, T)],
+    breakpoints: &[T],
+    smooth: bool,
+    offset: F,
+) -> Vec<Line<T>>
+where
+    T: Float + Euclid,
+    F: Fn(T, bool) -> (T, T),
+{
+    let two_pi = T::from(2f64 * std::f64::consts::PI).unwrap();
+    let half = T::from(0.5).unwrap();
+    // lines whose ends are closer than this are drawn as one stroke
+    let tolerance = T::from(1e-3).unwrap();
+    let normalize = |(x, y): (T, T)| {
+        let norm = x.hypot(y);
+        (x / norm, y / norm)
+    };
+
+    // normal and amplitude of every drawn line
+    let drawn: Vec<Option<((T, T), T)>> = lines
+        .iter()
+        .zip(oscillations)
+        .map(|((start, stop), oscillation)| {
+            let line_length = length(start, stop);
+            match oscillation {
+                (Some(amplitude), _) if line_length > T::zero() => Some((
+                    (
+                        (start.1 - stop.1) / line_length,
+                        (stop.0 - start.0) / line_length,
+                    ),
+                    *amplitude,
+                )),
+                _ => None,
+            }
+        })
+        .collect();
+    let connected = |i: usize| {
+        drawn[i].is_some()
+            && drawn[i + 1].is_some()
+            && length(&lines[i].1, &lines[i + 1].0) < tolerance
+    };
+    // normal and amplitude at the vertex between line `i` and the next one
+    let vertex = |i: usize| {
+        let ((normal, amplitude), (next_normal, next_amplitude)) =
+            (drawn[i].unwrap(), drawn[i + 1].unwrap());
+        let sum = (normal.0 + next_normal.0, normal.1 + next_normal.1);
+        // a reversal has no average direction
+        let normal = if sum.0.hypot(sum.1) > tolerance {
+            normalize(sum)
+        } else {
+            next_normal
+        };
+        (normal, (amplitude + next_amplitude) * half)
+    };
+
+    let mut result = Vec::new();
+    let mut stroke: Vec<(T, T)> = Vec::new();
+    // last point of the previous line, which closes the stroke if the next line does not continue it
+    let mut end: Option<(T, T)> = None;
+    let mut flush = |stroke: &mut Vec<(T, T)>, end: &mut Option<(T, T)>| {
+        stroke.extend(end.take());
+        result.extend(connect_points(stroke));
+        stroke.clear();
+    };
+
+    // phase within the current period
+    let mut phase = T::zero();
+    for (i, ((start, stop), oscillation)) in lines.iter().zip(oscillations).enumerate() {
+        if i == 0 || !connected(i - 1) {
+            flush(&mut stroke, &mut end);
+        }
+        let line_length = length(start, stop);
+        // the stroke does not depend on the sign of the angular frequency
+        let omega = oscillation.1.abs();
+        // periods per pixel
+        let frequency = omega / two_pi;
+        let (normal, amplitude) = match drawn[i] {
+            Some(drawn) => drawn,
+            None => {
+                phase = (phase + frequency * line_length).fract();
+                continue;
+            }
+        };
+        let (start_normal, start_amplitude) = if smooth && i > 0 && connected(i - 1) {
+            vertex(i - 1)
+        } else {
+            (normal, amplitude)
+        };
+        let (stop_normal, stop_amplitude) = if smooth && i + 1 < lines.len() && connected(i) {
+            vertex(i)
+        } else {
+            (normal, amplitude)
+        };
+        let point = |s: T, (along, across): (T, T)| {
+            let t = s / line_length;
+            let interpolated = (
+                start_normal.0 + t * (stop_normal.0 - start_normal.0),
+                start_normal.1 + t * (stop_normal.1 - start_normal.1),
+            );
+            // the interpolated normal is only short halfway through a reversal
+            let normal = if interpolated.0.hypot(interpolated.1) > tolerance {
+                normalize(interpolated)
+            } else {
+                normal
+            };
+            let amplitude = start_amplitude + t * (stop_amplitude - start_amplitude);
+            (
+                start.0
+                    + t * (stop.0 - start.0)
+                    + amplitude * (along * normal.1 + across * normal.0),
+                start.1
+                    + t * (stop.1 - start.1)
+                    + amplitude * (across * normal.1 - along * normal.0),
+            )
+        };
+
+        // positions along the line with the phase at each, breakpoints carry their exact phase
+        let mut samples: Vec<(T, T, bool)> = Vec::new();
+        let end_phase = phase + frequency * line_length;
+        if frequency > T::zero() {
+            for n in phase.floor().to_i64().unwrap()..=end_phase.floor().to_i64().unwrap() {
+                for breakpoint in breakpoints {
+                    let s = (T::from(n).unwrap() + *breakpoint - phase) / frequency;
+                    if s >= T::zero() && s < line_length {
+                        samples.push((s, *breakpoint, true));
+                    }
+                }
+            }
+        }
+        // the offset is sampled at least 16 times per period, at every pixel and such that the pen moves about
+        // one pixel at most between samples, corners and jumps are added exactly
+        let step = T::one()
+            .min(T::one() / (frequency * T::from(16).unwrap()))
+            .min(T::one() / (amplitude.max(start_amplitude).max(stop_amplitude) * omega));
+        let num = (line_length / step).ceil().to_usize().unwrap();
+        for i in 0..num {
+            let s = T::from(i).unwrap() * step;
+            if samples
+                .iter()
+                .all(|(other, _, _)| (*other - s).abs() > step * tolerance)
+            {
+                samples.push((s, (phase + frequency * s).fract(), false));
+            }
+        }
+        samples.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        for (s, sample_phase, is_breakpoint) in samples {
+            let before = offset(sample_phase, true);
+            let after = offset(sample_phase, false);
+            if is_breakpoint && before != after && !stroke.is_empty() {
+                stroke.push(point(s, before));
+            }
+            stroke.push(point(s, after));
+        }
+        // only the fraction is kept, which keeps the phase precise along long paths
+        phase = end_phase.fract();
+        end = Some(point(line_length, offset(phase, true)));
+    }
+    flush(&mut stroke, &mut end);
+    result
+}
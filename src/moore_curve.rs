@@ -0,0 +1,112 @@
+use hilbert_index::FromHilbertIndex;
+
+use num::{traits::Euclid, Float};
+use std::marker::PhantomData;
+
+use crate::{base_curve::BaseCurve, line_utils::set_scale};
+
+/// Moore curve, the closed variant of the Hilbert curve, see <https://en.wikipedia.org/wiki/Moore_curve>
+///
+/// The curve consists of four Hilbert curves of order `order - 1`, one per quadrant.
+/// Its last segment returns to the start point, so the path is a closed loop, also on non-square images.
+#[derive(Clone, Copy, Debug)]
+pub struct MooreCurve<T> {
+    order: usize,
+    _marker: PhantomData<T>,
+}
+
+pub struct MooreCurveIterator<T>
+where
+    T: Float,
+{
+    moore_curve: MooreCurve<T>,
+    index: usize,
+}
+
+impl<T> MooreCurve<T>
+where
+    T: Float,
+{
+    /// Returns the curve of given order, order 0 has no segments and is raised to 1
+    pub fn new(order: usize) -> MooreCurve<T> {
+        MooreCurve {
+            order: order.max(1),
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Returns the grid cell visited at given index
+    fn position(&self, index: usize) -> (usize, usize) {
+        let quadrant_order = self.order - 1;
+        let half = 1usize << quadrant_order;
+        let cells_per_quadrant = half * half;
+        let quadrant = (index / cells_per_quadrant) % 4;
+        // hilbert curve of the quadrant starts at (0, 0) and ends at (half - 1, 0)
+        let [x, y]: [usize; 2] = (index % cells_per_quadrant).from_hilbert_index(quadrant_order);
+        match quadrant {
+            0 => (half - 1 - y, x),
+            1 => (half - 1 - y, half + x),
+            2 => (half + y, 2 * half - 1 - x),
+            _ => (half + y, half - 1 - x),
+        }
+    }
+}
+
+impl<T> Iterator for MooreCurveIterator<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let num_cells = 4usize.pow(self.moore_curve.order as u32);
+        if self.index >= num_cells {
+            return None;
+        }
+        let side = T::from(1usize << self.moore_curve.order).unwrap();
+        let position_start = self.moore_curve.position(self.index);
+        let position_end = self.moore_curve.position((self.index + 1) % num_cells);
+        self.index += 1;
+        Some((
+            (
+                T::from(position_start.0).unwrap() / side,
+                T::from(position_start.1).unwrap() / side,
+            ),
+            (
+                T::from(position_end.0).unwrap() / side,
+                T::from(position_end.1).unwrap() / side,
+            ),
+        ))
+    }
+}
+
+impl<T> IntoIterator for MooreCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = MooreCurveIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MooreCurveIterator {
+            moore_curve: self,
+            index: 0,
+        }
+    }
+}
+
+impl<T> BaseCurve<T> for MooreCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// The loop is stretched onto the image, cropping it to the image would cut it open
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        let mut lines = self.segments();
+        set_scale(&mut lines, width, height);
+        lines
+    }
+}
@@ -0,0 +1,227 @@
+use num::{traits::Euclid, Float};
+
+use crate::{
+    base_curve::BaseCurve,
+    line_utils::{connect_points, set_scale, LineIterator},
+};
+
+/// Minimal number of samples per period of the fastest oscillation, fewer samples turn the figure into chords
+const SAMPLES_PER_PERIOD: usize = 16;
+
+/// Lissajous figure `x = sin(a t + phase)`, `y = sin(b t)`, see <https://en.wikipedia.org/wiki/Lissajous_curve>
+///
+/// The curve is sampled over `t` in `[0, 2 pi]`, so integer frequencies give a closed figure.
+#[derive(Clone, Copy, Debug)]
+pub struct LissajousCurve<T> {
+    frequencies: (T, T),
+    phase: T,
+    segments: usize,
+}
+
+/// Damped sinusoidal oscillation `amplitude * sin(frequency t + phase) * exp(-damping t)`
+#[derive(Clone, Copy, Debug)]
+pub struct Pendulum<T> {
+    pub amplitude: T,
+    pub frequency: T,
+    pub phase: T,
+    pub damping: T,
+}
+
+/// Harmonograph drawn by damped pendulums, see <https://en.wikipedia.org/wiki/Harmonograph>
+///
+/// The x and y coordinates are each the sum of the oscillations of their pendulums,
+/// so the figure spirals inwards as the pendulums lose energy.
+#[derive(Clone, Debug)]
+pub struct HarmonographCurve<T> {
+    x: Vec<Pendulum<T>>,
+    y: Vec<Pendulum<T>>,
+    duration: T,
+    segments: usize,
+}
+
+impl<T> LissajousCurve<T>
+where
+    T: Float,
+{
+    /// Returns the figure with frequencies `(a, b)` and `phase` (radians) of x, sampled into given number of segments,
+    /// but at least 16 per period of the faster oscillation
+    pub fn new(frequencies: (T, T), phase: T, segments: usize) -> LissajousCurve<T> {
+        let two_pi = T::from(2f64 * std::f64::consts::PI).unwrap();
+        LissajousCurve {
+            frequencies,
+            phase,
+            segments: segments.max(min_segments(
+                two_pi,
+                frequencies.0.abs().max(frequencies.1.abs()),
+            )),
+        }
+    }
+
+    fn points(&self) -> Vec<(T, T)> {
+        let two_pi = T::from(2f64 * std::f64::consts::PI).unwrap();
+        sample(two_pi, self.segments, |t| {
+            (
+                (self.frequencies.0 * t + self.phase).sin(),
+                (self.frequencies.1 * t).sin(),
+            )
+        })
+    }
+}
+
+impl<T> Pendulum<T>
+where
+    T: Float,
+{
+    pub fn new(amplitude: T, frequency: T, phase: T, damping: T) -> Pendulum<T> {
+        Pendulum {
+            amplitude,
+            frequency,
+            phase,
+            damping,
+        }
+    }
+
+    /// Returns the displacement of the pendulum at time `t`
+    fn displacement(&self, t: T) -> T {
+        self.amplitude * (self.frequency * t + self.phase).sin() * (-self.damping * t).exp()
+    }
+}
+
+impl<T> HarmonographCurve<T>
+where
+    T: Float,
+{
+    /// Returns the harmonograph of the pendulums moving x and y, run for `duration` and sampled into given number
+    /// of segments, but at least 16 per period of the fastest pendulum
+    pub fn new(
+        x: Vec<Pendulum<T>>,
+        y: Vec<Pendulum<T>>,
+        duration: T,
+        segments: usize,
+    ) -> HarmonographCurve<T> {
+        let max_frequency = x
+            .iter()
+            .chain(y.iter())
+            .fold(T::zero(), |max, pendulum| max.max(pendulum.frequency.abs()));
+        HarmonographCurve {
+            segments: segments.max(min_segments(duration, max_frequency)),
+            x,
+            y,
+            duration,
+        }
+    }
+
+    fn points(&self) -> Vec<(T, T)> {
+        let amplitude = |pendulums: &[Pendulum<T>]| {
+            pendulums
+                .iter()
+                .fold(T::zero(), |sum, pendulum| sum + pendulum.amplitude.abs())
+                .max(T::epsilon())
+        };
+        let amplitudes = (amplitude(&self.x), amplitude(&self.y));
+        let position = |pendulums: &[Pendulum<T>], t: T| {
+            pendulums
+                .iter()
+                .fold(T::zero(), |sum, pendulum| sum + pendulum.displacement(t))
+        };
+        sample(self.duration, self.segments, |t| {
+            (
+                position(&self.x, t) / amplitudes.0,
+                position(&self.y, t) / amplitudes.1,
+            )
+        })
+    }
+}
+
+/// Function returns the number of segments needed to sample an oscillation of given angular frequency
+/// [SAMPLES_PER_PERIOD] times per period over `duration`, at least one
+fn min_segments<T>(duration: T, frequency: T) -> usize
+where
+    T: Float,
+{
+    let two_pi = T::from(2f64 * std::f64::consts::PI).unwrap();
+    (duration.abs() * frequency / two_pi * T::from(SAMPLES_PER_PERIOD).unwrap())
+        .ceil()
+        .to_usize()
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Function samples a curve with coordinates in `[-1, 1]` at evenly spaced times in `[0, duration]`
+/// and maps it onto the unit square
+fn sample<T, F>(duration: T, segments: usize, curve: F) -> Vec<(T, T)>
+where
+    T: Float,
+    F: Fn(T) -> (T, T),
+{
+    let half = T::from(0.5).unwrap();
+    (0..=segments)
+        .map(|i| duration * T::from(i).unwrap() / T::from(segments).unwrap())
+        .map(|t| {
+            let (x, y) = curve(t);
+            ((x + T::one()) * half, (y + T::one()) * half)
+        })
+        .collect()
+}
+
+/// Function stretches the bounding box of the figure onto the image
+fn stretch<T>(points: &[(T, T)], width: usize, height: usize) -> Vec<((T, T), (T, T))>
+where
+    T: Float + Euclid,
+{
+    let mut lines = connect_points(points);
+    set_scale(&mut lines, width, height);
+    lines
+}
+
+impl<T> IntoIterator for LissajousCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        connect_points(&self.points()).into()
+    }
+}
+
+impl<T> IntoIterator for HarmonographCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        connect_points(&self.points()).into()
+    }
+}
+
+impl<T> BaseCurve<T> for LissajousCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// The figure is stretched onto the whole image instead of being cropped to a square
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        stretch(&self.points(), width, height)
+    }
+}
+
+impl<T> BaseCurve<T> for HarmonographCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        connect_points(&self.points())
+    }
+
+    /// The figure is stretched onto the whole image instead of being cropped to a square
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        stretch(&self.points(), width, height)
+    }
+}
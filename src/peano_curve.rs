@@ -0,0 +1,110 @@
+use num::{traits::Euclid, Float};
+use std::marker::PhantomData;
+
+use crate::base_curve::BaseCurve;
+
+/// Peano curve filling a grid of 3^order x 3^order cells, see <https://en.wikipedia.org/wiki/Peano_curve>
+#[derive(Clone, Copy, Debug)]
+pub struct PeanoCurve<T> {
+    order: usize,
+    _marker: PhantomData<T>,
+}
+
+pub struct PeanoCurveIterator<T>
+where
+    T: Float,
+{
+    peano_curve: PeanoCurve<T>,
+    index: usize,
+}
+
+impl<T> PeanoCurve<T>
+where
+    T: Float,
+{
+    /// Returns the curve of given order, order 0 has no segments and is raised to 1
+    pub fn new(order: usize) -> PeanoCurve<T> {
+        PeanoCurve {
+            order: order.max(1),
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Returns the grid cell visited at given index.
+    ///
+    /// The base 3 digits of the index alternately select the column and row within the next finer 3x3 block,
+    /// a digit is mirrored (d -> 2 - d) if the digits of the other axis summed up so far are odd.
+    fn position(&self, index: usize) -> (usize, usize) {
+        let mut digits: Vec<usize> = Vec::with_capacity(2 * self.order);
+        let mut rest = index;
+        for _ in 0..2 * self.order {
+            digits.push(rest % 3);
+            rest /= 3;
+        }
+        digits.reverse();
+
+        let (mut x, mut y) = (0, 0);
+        let (mut sum_x, mut sum_y) = (0, 0);
+        for pair in digits.chunks(2) {
+            let digit_x = if sum_y % 2 == 1 { 2 - pair[0] } else { pair[0] };
+            sum_x += pair[0];
+            let digit_y = if sum_x % 2 == 1 { 2 - pair[1] } else { pair[1] };
+            sum_y += pair[1];
+            x = 3 * x + digit_x;
+            y = 3 * y + digit_y;
+        }
+        (x, y)
+    }
+}
+
+impl<T> Iterator for PeanoCurveIterator<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let num_cells = 9usize.pow(self.peano_curve.order as u32);
+        if self.index + 1 >= num_cells {
+            return None;
+        }
+        let side = T::from(3usize.pow(self.peano_curve.order as u32)).unwrap();
+        let position_start = self.peano_curve.position(self.index);
+        let position_end = self.peano_curve.position(self.index + 1);
+        self.index += 1;
+        Some((
+            (
+                T::from(position_start.0).unwrap() / side,
+                T::from(position_start.1).unwrap() / side,
+            ),
+            (
+                T::from(position_end.0).unwrap() / side,
+                T::from(position_end.1).unwrap() / side,
+            ),
+        ))
+    }
+}
+
+impl<T> IntoIterator for PeanoCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = PeanoCurveIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PeanoCurveIterator {
+            peano_curve: self,
+            index: 0,
+        }
+    }
+}
+
+impl<T> BaseCurve<T> for PeanoCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+}
@@ -0,0 +1,145 @@
+use num::{traits::Euclid, Float};
+
+use crate::{base_curve::BaseCurve, line_utils::LineIterator};
+
+/// Parallel scan lines traversed in alternating direction (boustrophedon) and connected into one continuous path
+///
+/// The lines run at an angle to the x axis and are clipped to the image.
+#[derive(Clone, Copy, Debug)]
+pub struct ScanlineCurve<T> {
+    spacing: T,
+    angle: T,
+}
+
+impl<T> ScanlineCurve<T>
+where
+    T: Float,
+{
+    /// Returns scan lines `spacing` apart, given as fraction of the larger image side,
+    /// running at `angle` (radians) to the x axis. The spacing is capped at 1, a spacing that is not positive draws a
+    /// single line.
+    pub fn new(spacing: T, angle: T) -> ScanlineCurve<T> {
+        let spacing = if spacing > T::zero() {
+            spacing.min(T::one())
+        } else {
+            T::one()
+        };
+        ScanlineCurve { spacing, angle }
+    }
+
+    /// Returns the start and stop points of the scan lines on an image of given size, in drawing order
+    pub(crate) fn rows(&self, width: T, height: T) -> Vec<((T, T), (T, T))> {
+        let zero = T::zero();
+        let two = T::from(2).unwrap();
+        let spacing = self.spacing * width.max(height);
+        let direction = (self.angle.cos(), self.angle.sin());
+        let normal = (-direction.1, direction.0);
+        let center = (width / two, height / two);
+
+        // offsets of the image corners along the normal bound the lines needed to cover the image
+        let offsets: Vec<T> = [(zero, zero), (width, zero), (zero, height), (width, height)]
+            .iter()
+            .map(|corner| (corner.0 - center.0) * normal.0 + (corner.1 - center.1) * normal.1)
+            .collect();
+        let min_offset = offsets
+            .iter()
+            .fold(T::infinity(), |min, offset| min.min(*offset));
+        let max_offset = offsets
+            .iter()
+            .fold(T::neg_infinity(), |max, offset| max.max(*offset));
+
+        let num_rows = ((max_offset - min_offset) / spacing).round().max(T::one());
+        // the rows are centered on the image, a margin of half the spacing if they fill it exactly
+        let first_offset = (min_offset + max_offset - (num_rows - T::one()) * spacing) / two;
+        let mut rows = Vec::new();
+        for i in 0..num_rows.to_usize().unwrap() {
+            let offset = first_offset + T::from(i).unwrap() * spacing;
+            let origin = (center.0 + offset * normal.0, center.1 + offset * normal.1);
+            if let Some((t_start, t_stop)) = clip(origin, direction, width, height) {
+                let start = (
+                    origin.0 + t_start * direction.0,
+                    origin.1 + t_start * direction.1,
+                );
+                let stop = (
+                    origin.0 + t_stop * direction.0,
+                    origin.1 + t_stop * direction.1,
+                );
+                if rows.len() % 2 == 0 {
+                    rows.push((start, stop));
+                } else {
+                    rows.push((stop, start));
+                }
+            }
+        }
+        rows
+    }
+
+    /// Returns the scan lines joined by connecting segments
+    fn lines(&self, width: T, height: T) -> Vec<((T, T), (T, T))> {
+        let rows = self.rows(width, height);
+        let mut lines = Vec::with_capacity(2 * rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                lines.push((rows[i - 1].1, row.0));
+            }
+            lines.push(*row);
+        }
+        lines
+    }
+}
+
+/// Returns the parameter range in which the line `origin + t * direction` lies within the image
+fn clip<T>(origin: (T, T), direction: (T, T), width: T, height: T) -> Option<(T, T)>
+where
+    T: Float,
+{
+    let mut t_min = T::neg_infinity();
+    let mut t_max = T::infinity();
+    for (position, step, size) in [
+        (origin.0, direction.0, width),
+        (origin.1, direction.1, height),
+    ] {
+        if step.abs() < T::epsilon() {
+            if position < T::zero() || position > size {
+                return None;
+            }
+            continue;
+        }
+        let t_0 = (T::zero() - position) / step;
+        let t_1 = (size - position) / step;
+        t_min = t_min.max(t_0.min(t_1));
+        t_max = t_max.min(t_0.max(t_1));
+    }
+    if t_max > t_min {
+        Some((t_min, t_max))
+    } else {
+        None
+    }
+}
+
+impl<T> IntoIterator for ScanlineCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    /// Iterates over the scan lines and their connections on the unit square
+    fn into_iter(self) -> Self::IntoIter {
+        self.lines(T::one(), T::one()).into()
+    }
+}
+
+impl<T> BaseCurve<T> for ScanlineCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// The lines are clipped to the image itself, so the path covers it completely without leaving it
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        self.lines(T::from(width).unwrap(), T::from(height).unwrap())
+    }
+}
@@ -0,0 +1,61 @@
+use num::{traits::Euclid, Float};
+use std::{collections::HashMap, marker::PhantomData};
+
+use crate::{
+    base_curve::BaseCurve,
+    l_system,
+    line_utils::{connect_points, LineIterator},
+};
+
+/// Closed Sierpinski curve, see <https://en.wikipedia.org/wiki/Sierpi%C5%84ski_curve>
+///
+/// The curve consists of 4^(order + 1) segments of equal length, running either diagonally or along the axes.
+/// Its last segment returns to the start point.
+#[derive(Clone, Copy, Debug)]
+pub struct SierpinskiCurve<T> {
+    order: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> SierpinskiCurve<T>
+where
+    T: Float,
+{
+    pub fn new(order: usize) -> SierpinskiCurve<T> {
+        SierpinskiCurve {
+            order,
+            _marker: PhantomData {},
+        }
+    }
+}
+
+impl<T> IntoIterator for SierpinskiCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let rules = HashMap::from([('X', "XF+G+XF--F--XF+G+X")]);
+        let commands = l_system::expand("F--XF--F--XF", &rules, self.order);
+        let mut points = l_system::to_unit_square(&l_system::turtle(&commands, 45.0, &['F', 'G']));
+        // remove the rounding error accumulated by the turtle to close the loop exactly
+        let last = points.len() - 1;
+        points[last] = points[0];
+        let points: Vec<(T, T)> = points
+            .iter()
+            .map(|(x, y)| (T::from(*x).unwrap(), T::from(*y).unwrap()))
+            .collect();
+        connect_points(&points).into()
+    }
+}
+
+impl<T> BaseCurve<T> for SierpinskiCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+}
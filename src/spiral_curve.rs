@@ -0,0 +1,98 @@
+use num::{traits::Euclid, Float};
+
+use crate::{
+    base_curve::BaseCurve,
+    line_utils::{clip, connect_points, LineIterator},
+};
+
+/// Archimedean spiral winding outwards from a center, see <https://en.wikipedia.org/wiki/Archimedean_spiral>
+///
+/// The radius grows linearly with the angle such that the last turn reaches the image corner farthest from the center,
+/// so the spiral covers the whole image. Turns are clipped to the image, they stay one path up to the closest border.
+#[derive(Clone, Copy, Debug)]
+pub struct SpiralCurve<T> {
+    turns: usize,
+    center: (T, T),
+    clockwise: bool,
+}
+
+impl<T> SpiralCurve<T>
+where
+    T: Float,
+{
+    /// Returns a spiral of given number of turns around `center`, given as fraction of the image width and height and
+    /// clamped to the image. `clockwise` refers to the image, whose y axis points down.
+    pub fn new(turns: usize, center: (T, T), clockwise: bool) -> SpiralCurve<T> {
+        let clamp = |value: T| value.max(T::zero()).min(T::one());
+        SpiralCurve {
+            turns: turns.max(1),
+            center: (clamp(center.0), clamp(center.1)),
+            clockwise,
+        }
+    }
+
+    /// Returns the points of the unclipped spiral on an image of given size
+    fn points(&self, width: T, height: T) -> Vec<(T, T)> {
+        let zero = T::zero();
+        let two = T::from(2).unwrap();
+        let two_pi = T::from(2f64 * std::f64::consts::PI).unwrap();
+        let center = (self.center.0 * width, self.center.1 * height);
+        let max_radius = center
+            .0
+            .max(width - center.0)
+            .hypot(center.1.max(height - center.1));
+        let spacing = max_radius / T::from(self.turns).unwrap();
+        let max_angle = two_pi * T::from(self.turns).unwrap();
+        // segments are at most half the spacing long and span at most 1/32 of a turn
+        let max_step = two_pi / T::from(32).unwrap();
+        let sign = if self.clockwise { T::one() } else { -T::one() };
+
+        let mut points = Vec::new();
+        let mut angle = zero;
+        loop {
+            let radius = spacing * angle / two_pi;
+            points.push((
+                center.0 + radius * angle.cos(),
+                center.1 + sign * radius * angle.sin(),
+            ));
+            if angle >= max_angle {
+                break;
+            }
+            let step = max_step.min(spacing / two / radius.max(spacing / two));
+            angle = (angle + step).min(max_angle);
+        }
+        points
+    }
+}
+
+impl<T> IntoIterator for SpiralCurve<T>
+where
+    T: Float + Euclid,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    /// Iterates over the segments of the spiral on the unit square
+    fn into_iter(self) -> Self::IntoIter {
+        let mut segments = connect_points(&self.points(T::one(), T::one()));
+        clip(&mut segments, 1, 1);
+        segments.into()
+    }
+}
+
+impl<T> BaseCurve<T> for SpiralCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// The spiral is laid out on the image itself to stay circular
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        let mut lines =
+            connect_points(&self.points(T::from(width).unwrap(), T::from(height).unwrap()));
+        clip(&mut lines, width, height);
+        lines
+    }
+}
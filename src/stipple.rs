@@ -0,0 +1,225 @@
+use image::{ImageBuffer, Luma};
+use num::{traits::Euclid, Float};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::marker::PhantomData;
+
+/// Dots whose density follows the darkness of an image, see <https://en.wikipedia.org/wiki/Stippling>
+///
+/// The dots are sampled with probability proportional to the darkness of the pixels and then spread evenly with
+/// Lloyd's algorithm on the weighted Voronoi diagram: every dot moves to the darkness-weighted centroid of the pixels
+/// closest to it. The sampling is deterministic for a given seed.
+#[derive(Clone, Copy, Debug)]
+pub struct Stipple<'a, T> {
+    image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+    num_points: usize,
+    iterations: usize,
+    seed: u64,
+    _marker: PhantomData<T>,
+}
+
+/// Shape drawn for each stipple dot
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DotShape {
+    /// Closed polygon approximating a circle
+    Circle,
+    /// Short horizontal stroke
+    Segment,
+}
+
+/// Points bucketed by square cells for nearest neighbour queries
+struct Buckets {
+    cell_size: f64,
+    num_x: usize,
+    num_y: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+impl Buckets {
+    fn new(points: &[(f64, f64)], width: usize, height: usize, cell_size: f64) -> Buckets {
+        let num_x = (width as f64 / cell_size).ceil() as usize + 1;
+        let num_y = (height as f64 / cell_size).ceil() as usize + 1;
+        let mut buckets = Buckets {
+            cell_size,
+            num_x,
+            num_y,
+            cells: vec![Vec::new(); num_x * num_y],
+        };
+        for (index, point) in points.iter().enumerate() {
+            let (x, y) = buckets.cell(*point);
+            buckets.cells[y * num_x + x].push(index);
+        }
+        buckets
+    }
+
+    fn cell(&self, point: (f64, f64)) -> (usize, usize) {
+        (
+            ((point.0 / self.cell_size).max(0.) as usize).min(self.num_x - 1),
+            ((point.1 / self.cell_size).max(0.) as usize).min(self.num_y - 1),
+        )
+    }
+
+    /// Returns the index of the point closest to `point`, searching rings of cells around it
+    fn nearest(&self, points: &[(f64, f64)], point: (f64, f64)) -> Option<usize> {
+        let (x, y) = self.cell(point);
+        let mut best: Option<(usize, f64)> = None;
+        for ring in 0..self.num_x.max(self.num_y) as i64 {
+            for dy in -ring..=ring {
+                for dx in -ring..=ring {
+                    if dx.abs() != ring && dy.abs() != ring {
+                        continue;
+                    }
+                    let (cell_x, cell_y) = (x as i64 + dx, y as i64 + dy);
+                    if cell_x < 0
+                        || cell_y < 0
+                        || cell_x >= self.num_x as i64
+                        || cell_y >= self.num_y as i64
+                    {
+                        continue;
+                    }
+                    for index in &self.cells[cell_y as usize * self.num_x + cell_x as usize] {
+                        let other = points[*index];
+                        let distance = (other.0 - point.0).hypot(other.1 - point.1);
+                        if best.is_none_or(|(_, best)| distance < best) {
+                            best = Some((*index, distance));
+                        }
+                    }
+                }
+            }
+            // cells of the next ring are at least `ring` cells away
+            if let Some((index, distance)) = best {
+                if distance <= ring as f64 * self.cell_size {
+                    return Some(index);
+                }
+            }
+        }
+        best.map(|(index, _)| index)
+    }
+}
+
+impl<'a, T> Stipple<'a, T>
+where
+    T: Float + Euclid,
+{
+    /// Returns a stipple of `num_points` dots relaxed for given number of iterations
+    pub fn new(
+        image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+        num_points: usize,
+        iterations: usize,
+        seed: u64,
+    ) -> Stipple<'a, T> {
+        Stipple {
+            image,
+            num_points,
+            iterations,
+            seed,
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Returns the dots in pixel coordinates of the image, a white image has no dots
+    pub fn points(&self) -> Vec<(T, T)> {
+        let (width, height) = (self.image.width(), self.image.height());
+        let min = *self.image.iter().min().unwrap() as f64;
+        let max = *self.image.iter().max().unwrap() as f64;
+        if self.num_points == 0 {
+            return Vec::new();
+        }
+        // darkness in [0, 1] after heightening the contrast like the approximation, the dots are black.
+        // Without contrast to heighten the absolute darkness is used.
+        let (white, range) = if max > min {
+            (max, max - min)
+        } else {
+            (255., 255.)
+        };
+        let darkness = |x: u32, y: u32| (white - self.image.get_pixel(x, y).0[0] as f64) / range;
+        // a pixel is picked with probability proportional to its darkness from the cumulative darkness
+        let cumulative: Vec<f64> = self
+            .image
+            .enumerate_pixels()
+            .scan(0., |sum, (x, y, _)| {
+                *sum += darkness(x, y);
+                Some(*sum)
+            })
+            .collect();
+        let total = *cumulative.last().unwrap();
+        if total <= 0. {
+            return Vec::new();
+        }
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut points: Vec<(f64, f64)> = (0..self.num_points)
+            .map(|_| {
+                let target = rng.random::<f64>() * total;
+                let index = cumulative
+                    .partition_point(|sum| *sum <= target)
+                    .min(cumulative.len() - 1);
+                let (x, y) = (index as u32 % width, index as u32 / width);
+                (x as f64 + rng.random::<f64>(), y as f64 + rng.random::<f64>())
+            })
+            .collect();
+
+        let cell_size = ((width * height) as f64 / self.num_points as f64)
+            .sqrt()
+            .max(1.);
+        for _ in 0..self.iterations {
+            let buckets = Buckets::new(&points, width as usize, height as usize, cell_size);
+            let mut centroids = vec![(0f64, 0f64, 0f64); points.len()];
+            for (x, y, _) in self.image.enumerate_pixels() {
+                let weight = darkness(x, y);
+                if weight == 0. {
+                    continue;
+                }
+                let center = (x as f64 + 0.5, y as f64 + 0.5);
+                if let Some(index) = buckets.nearest(&points, center) {
+                    centroids[index].0 += weight * center.0;
+                    centroids[index].1 += weight * center.1;
+                    centroids[index].2 += weight;
+                }
+            }
+            for (point, (x, y, weight)) in points.iter_mut().zip(centroids) {
+                // dots without dark pixels in their cell stay where they are
+                if weight > 0. {
+                    *point = (x / weight, y / weight);
+                }
+            }
+        }
+        points
+            .iter()
+            .map(|(x, y)| (T::from(*x).unwrap(), T::from(*y).unwrap()))
+            .collect()
+    }
+}
+
+/// Function draws a dot of given radius at every point
+pub fn draw_dots<T>(points: &[(T, T)], radius: T, shape: DotShape) -> Vec<((T, T), (T, T))>
+where
+    T: Float,
+{
+    let two_pi = T::from(2f64 * std::f64::consts::PI).unwrap();
+    // sides of at most one pixel, but at least a hexagon
+    let sides = (two_pi * radius).ceil().to_usize().unwrap_or(0).max(6);
+    points
+        .iter()
+        .flat_map(|center| match shape {
+            DotShape::Circle => (0..sides)
+                .map(|i| {
+                    let angle = |i: usize| two_pi * T::from(i).unwrap() / T::from(sides).unwrap();
+                    let (start, stop) = (angle(i), angle((i + 1) % sides));
+                    (
+                        (
+                            center.0 + radius * start.cos(),
+                            center.1 + radius * start.sin(),
+                        ),
+                        (
+                            center.0 + radius * stop.cos(),
+                            center.1 + radius * stop.sin(),
+                        ),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            DotShape::Segment => {
+                vec![((center.0 - radius, center.1), (center.0 + radius, center.1))]
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,274 @@
+use image::{ImageBuffer, Luma};
+use imageproc::filter::gaussian_blur_f32;
+use num::{traits::Euclid, Float};
+use std::collections::HashMap;
+
+use crate::{base_curve::BaseCurve, image_utils::get_brightness, line_utils::connect_points};
+
+/// Direction of the streamlines relative to the brightness gradient
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamlineDirection {
+    /// Streamlines run from dark to bright regions
+    Gradient,
+    /// Streamlines run along lines of equal brightness, like the strokes of an engraving
+    Contour,
+}
+
+/// Streamlines following the brightness gradient of an image
+///
+/// Seeds are placed on an even grid, each seed is traced forwards and backwards until the streamline leaves the image
+/// or comes closer to another streamline than half the separation. Seeds closer than the separation to an existing
+/// streamline are skipped. In flat regions the streamlines keep their direction.
+///
+/// Unlike the other curves the result consists of many separate polylines.
+#[derive(Clone, Copy, Debug)]
+pub struct StreamlineCurve<'a, T> {
+    image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+    separation: T,
+    direction: StreamlineDirection,
+}
+
+/// Points of the traced streamlines bucketed by cells of the size of the separation
+struct Grid<T> {
+    cell_size: T,
+    cells: HashMap<(i64, i64), Vec<GridPoint<T>>>,
+}
+
+/// Point of a streamline, `position` counts the steps from the seed, negative when traced backwards
+#[derive(Clone, Copy)]
+struct GridPoint<T> {
+    point: (T, T),
+    streamline: usize,
+    position: i64,
+}
+
+impl<T> Grid<T>
+where
+    T: Float,
+{
+    fn new(cell_size: T) -> Grid<T> {
+        Grid {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, point: (T, T)) -> (i64, i64) {
+        (
+            (point.0 / self.cell_size).floor().to_i64().unwrap(),
+            (point.1 / self.cell_size).floor().to_i64().unwrap(),
+        )
+    }
+
+    fn insert(&mut self, grid_point: GridPoint<T>) {
+        let cell = self.cell(grid_point.point);
+        self.cells.entry(cell).or_default().push(grid_point);
+    }
+
+    /// Returns whether a point closer than `distance` exists, ignoring the points of `streamline`
+    /// less than `skip` steps away from `position`. `distance` must not exceed the cell size.
+    fn is_occupied(
+        &self,
+        point: (T, T),
+        distance: T,
+        streamline: usize,
+        position: i64,
+        skip: i64,
+    ) -> bool {
+        let cell = self.cell(point);
+        (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (cell.0 + dx, cell.1 + dy)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .any(|other| {
+                (other.streamline != streamline || (other.position - position).abs() > skip)
+                    && (other.point.0 - point.0).hypot(other.point.1 - point.1) < distance
+            })
+    }
+}
+
+impl<'a, T> StreamlineCurve<'a, T>
+where
+    T: Float + Euclid,
+{
+    /// Returns streamlines on `image` that keep `separation` apart, given as fraction of the larger image side
+    pub fn new(
+        image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+        separation: T,
+        direction: StreamlineDirection,
+    ) -> StreamlineCurve<'a, T> {
+        StreamlineCurve {
+            image,
+            separation,
+            direction,
+        }
+    }
+
+    /// Returns the streamlines in pixel coordinates of the image
+    pub fn polylines(&self) -> Vec<Vec<(T, T)>> {
+        let (width, height) = (
+            T::from(self.image.width()).unwrap(),
+            T::from(self.image.height()).unwrap(),
+        );
+        let half = T::from(0.5).unwrap();
+        let separation = (self.separation * width.max(height)).max(T::one());
+        let step = half.min(separation * half);
+        // the gradient of the blurred image varies on the scale of the separation
+        let blurred = gaussian_blur_f32(
+            self.image,
+            (separation * half).max(T::one()).to_f32().unwrap(),
+        );
+        let field = |point: (T, T)| self.field(&blurred, point);
+        // steps along the own streamline after which it may not come close to itself again
+        let skip = (T::from(2).unwrap() * separation / step)
+            .ceil()
+            .to_i64()
+            .unwrap();
+        let max_steps = (T::from(4).unwrap() * (width + height) / step)
+            .to_i64()
+            .unwrap();
+        let inside = |point: (T, T)| {
+            point.0 >= T::zero() && point.1 >= T::zero() && point.0 <= width && point.1 <= height
+        };
+
+        let mut grid = Grid::new(separation);
+        let mut polylines = Vec::new();
+        let seeds = |size: T| {
+            let num = (size / separation).floor().to_usize().unwrap().max(1);
+            (0..num).map(move |i| (T::from(i).unwrap() + half) * separation)
+        };
+        for (y, x) in seeds(height).flat_map(|y| seeds(width).map(move |x| (y, x))) {
+            let seed = (x, y);
+            let streamline = polylines.len();
+            if grid.is_occupied(seed, separation, streamline, 0, 0) {
+                continue;
+            }
+            let initial = field(seed).unwrap_or((T::one(), T::zero()));
+            let mut halves = Vec::new();
+            for sign in [T::one(), -T::one()] {
+                let mut direction = (sign * initial.0, sign * initial.1);
+                let mut point = seed;
+                let mut points = Vec::new();
+                for i in 1..max_steps {
+                    let position = if sign > T::zero() { i } else { -i };
+                    // midpoint integration, the field is aligned with the current direction
+                    let mid = align(
+                        field((
+                            point.0 + direction.0 * step * half,
+                            point.1 + direction.1 * step * half,
+                        )),
+                        direction,
+                    );
+                    let next = (point.0 + mid.0 * step, point.1 + mid.1 * step);
+                    if !inside(next)
+                        || grid.is_occupied(next, separation * half, streamline, position, skip)
+                    {
+                        break;
+                    }
+                    direction = align(field(next), direction);
+                    point = next;
+                    points.push(point);
+                    grid.insert(GridPoint {
+                        point,
+                        streamline,
+                        position,
+                    });
+                }
+                halves.push(points);
+            }
+            let backward = halves.pop().unwrap();
+            let forward = halves.pop().unwrap();
+            let mut polyline: Vec<(T, T)> = backward.into_iter().rev().collect();
+            polyline.push(seed);
+            polyline.extend(forward);
+            grid.insert(GridPoint {
+                point: seed,
+                streamline,
+                position: 0,
+            });
+            polylines.push(polyline);
+        }
+        polylines.retain(|polyline| polyline.len() > 1);
+        polylines
+    }
+
+    /// Returns the unit direction of the streamlines at the given point, if the brightness changes there
+    fn field(&self, image: &ImageBuffer<Luma<u8>, Vec<u8>>, point: (T, T)) -> Option<(T, T)> {
+        let one = T::one();
+        let clamp = |value: T, size: u32| value.max(T::zero()).min(T::from(size - 1).unwrap());
+        let brightness =
+            |x: T, y: T| get_brightness(image, clamp(x, image.width()), clamp(y, image.height()));
+        let gradient = (
+            brightness(point.0 + one, point.1) - brightness(point.0 - one, point.1),
+            brightness(point.0, point.1 + one) - brightness(point.0, point.1 - one),
+        );
+        let magnitude = gradient.0.hypot(gradient.1);
+        // brightness differences below one step of the 8 bit image are noise
+        if magnitude < one {
+            return None;
+        }
+        let gradient = (gradient.0 / magnitude, gradient.1 / magnitude);
+        Some(match self.direction {
+            StreamlineDirection::Gradient => gradient,
+            StreamlineDirection::Contour => (-gradient.1, gradient.0),
+        })
+    }
+
+    /// Returns the segments of all streamlines in pixel coordinates of the image
+    fn lines(&self) -> Vec<((T, T), (T, T))> {
+        self.polylines()
+            .iter()
+            .flat_map(|points| connect_points(points))
+            .collect()
+    }
+}
+
+/// Function flips the field direction to continue in `direction`, which is kept where the field is undefined
+fn align<T>(field: Option<(T, T)>, direction: (T, T)) -> (T, T)
+where
+    T: Float,
+{
+    match field {
+        Some(field) if field.0 * direction.0 + field.1 * direction.1 < T::zero() => {
+            (-field.0, -field.1)
+        }
+        Some(field) => field,
+        None => direction,
+    }
+}
+
+impl<'a, T> BaseCurve<T> for StreamlineCurve<'a, T>
+where
+    T: Float + Euclid,
+{
+    /// Returns the segments of the streamlines scaled by the larger image side
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        let scale = T::from(self.image.width().max(self.image.height())).unwrap();
+        self.lines()
+            .iter()
+            .map(|(start, stop)| {
+                (
+                    (start.0 / scale, start.1 / scale),
+                    (stop.0 / scale, stop.1 / scale),
+                )
+            })
+            .collect()
+    }
+
+    /// The streamlines are traced on the image itself and only scaled to the requested size
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        let scale = (
+            T::from(width).unwrap() / T::from(self.image.width()).unwrap(),
+            T::from(height).unwrap() / T::from(self.image.height()).unwrap(),
+        );
+        self.lines()
+            .iter()
+            .map(|(start, stop)| {
+                (
+                    (start.0 * scale.0, start.1 * scale.1),
+                    (stop.0 * scale.0, stop.1 * scale.1),
+                )
+            })
+            .collect()
+    }
+}
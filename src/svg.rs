@@ -0,0 +1,85 @@
+use std::{fmt::Write, fs, io, path::Path};
+
+use num::{traits::Euclid, Float};
+
+use crate::line_utils::{join_lines, Line};
+
+/// Options of the svg document
+#[derive(Clone, Copy, Debug)]
+pub struct SvgOptions {
+    /// stroke width in image pixels
+    pub stroke_width: f64,
+    /// width of the document in mm
+    pub width_mm: f64,
+    /// height of the document in mm
+    pub height_mm: f64,
+    /// maximal distance between the end of a line and the start of the next one to join them into one polyline
+    pub join_tolerance: f64,
+}
+
+impl SvgOptions {
+    /// Returns options for a document of the given size with a stroke width of one pixel
+    pub fn new(width_mm: f64, height_mm: f64) -> SvgOptions {
+        SvgOptions {
+            stroke_width: 1.0,
+            width_mm,
+            height_mm,
+            join_tolerance: 1e-3,
+        }
+    }
+
+    /// Returns options for a document of the given width, the height follows the aspect ratio of the image
+    pub fn with_width(width_mm: f64, image_width: u32, image_height: u32) -> SvgOptions {
+        SvgOptions::new(
+            width_mm,
+            width_mm * image_height as f64 / image_width as f64,
+        )
+    }
+}
+
+/// Function writes lines as svg document whose viewBox matches an image of given width and height
+pub fn to_svg<T>(lines: &[Line<T>], width: u32, height: u32, options: &SvgOptions) -> String
+where
+    T: Float + Euclid,
+{
+    let mut svg = String::new();
+    writeln!(svg, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}mm" height="{}mm" viewBox="0 0 {} {}">"#,
+        options.width_mm, options.height_mm, width, height
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<g fill="none" stroke="black" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round">"#,
+        options.stroke_width
+    )
+    .unwrap();
+    for polyline in join_lines(lines, T::from(options.join_tolerance).unwrap()) {
+        let points = polyline
+            .iter()
+            .map(|(x, y)| format!("{:.3},{:.3}", x.to_f64().unwrap(), y.to_f64().unwrap()))
+            .collect::<Vec<String>>()
+            .join(" ");
+        writeln!(svg, r#"<polyline points="{}"/>"#, points).unwrap();
+    }
+    writeln!(svg, "</g>").unwrap();
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
+
+/// Function saves lines as svg document, see [to_svg]
+pub fn save_svg<T, P>(
+    path: P,
+    lines: &[Line<T>],
+    width: u32,
+    height: u32,
+    options: &SvgOptions,
+) -> io::Result<()>
+where
+    T: Float + Euclid,
+    P: AsRef<Path>,
+{
+    fs::write(path, to_svg(lines, width, height, options))
+}
@@ -0,0 +1,224 @@
+use image::{ImageBuffer, Luma};
+use num::{traits::Euclid, Float};
+
+use crate::{
+    base_curve::BaseCurve,
+    line_utils::{connect_points, LineIterator},
+    stipple::Stipple,
+};
+
+/// Number of closest points considered as new neighbours by the 2-opt improvement
+const NUM_NEIGHBOURS: usize = 8;
+
+/// Maximal number of passes of the 2-opt improvement over all points
+const MAX_PASSES: usize = 50;
+
+/// Path visiting the dots of a [Stipple] once each, see <https://en.wikipedia.org/wiki/Travelling_salesman_problem>
+///
+/// The path is constructed by repeatedly moving to the closest unvisited dot and then shortened with 2-opt moves,
+/// which reverse a part of the path whenever that makes it shorter. Like the stipple it is deterministic for a given seed.
+#[derive(Clone, Copy, Debug)]
+pub struct TspCurve<'a, T> {
+    stipple: Stipple<'a, T>,
+    image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+}
+
+impl<'a, T> TspCurve<'a, T>
+where
+    T: Float + Euclid,
+{
+    /// Returns the path through `num_points` stipple dots, see [Stipple::new]
+    pub fn new(
+        image: &'a ImageBuffer<Luma<u8>, Vec<u8>>,
+        num_points: usize,
+        iterations: usize,
+        seed: u64,
+    ) -> TspCurve<'a, T> {
+        TspCurve {
+            stipple: Stipple::new(image, num_points, iterations, seed),
+            image,
+        }
+    }
+
+    /// Returns the dots in the order of the path in pixel coordinates of the image,
+    /// an image of uniform brightness has no dots and gives an empty path
+    pub fn tour(&self) -> Vec<(T, T)> {
+        let points = self.stipple.points();
+        let mut path = nearest_neighbour_path(&points);
+        two_opt(&points, &mut path);
+        path.iter().map(|index| points[*index]).collect()
+    }
+
+    /// Returns the segments of the path in pixel coordinates of the image
+    fn lines(&self) -> Vec<((T, T), (T, T))> {
+        connect_points(&self.tour())
+    }
+}
+
+fn distance<T>(a: (T, T), b: (T, T)) -> T
+where
+    T: Float,
+{
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// Function returns the indices of the points in the order visited by always moving to the closest unvisited point
+fn nearest_neighbour_path<T>(points: &[(T, T)]) -> Vec<usize>
+where
+    T: Float,
+{
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let mut visited = vec![false; points.len()];
+    let mut path = vec![0];
+    visited[0] = true;
+    for _ in 1..points.len() {
+        let current = points[*path.last().unwrap()];
+        let next = (0..points.len())
+            .filter(|index| !visited[*index])
+            .min_by(|a, b| {
+                distance(current, points[*a])
+                    .partial_cmp(&distance(current, points[*b]))
+                    .unwrap()
+            })
+            .unwrap();
+        visited[next] = true;
+        path.push(next);
+    }
+    path
+}
+
+/// Function returns the indices of the closest points of every point, excluding the point itself
+fn neighbours<T>(points: &[(T, T)]) -> Vec<Vec<usize>>
+where
+    T: Float,
+{
+    points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            let mut others: Vec<usize> =
+                (0..points.len()).filter(|other| *other != index).collect();
+            let by_distance = |a: &usize, b: &usize| {
+                distance(*point, points[*a])
+                    .partial_cmp(&distance(*point, points[*b]))
+                    .unwrap()
+            };
+            if others.len() > NUM_NEIGHBOURS {
+                others.select_nth_unstable_by(NUM_NEIGHBOURS, by_distance);
+                others.truncate(NUM_NEIGHBOURS);
+            }
+            others.sort_by(by_distance);
+            others
+        })
+        .collect()
+}
+
+/// Function shortens the open path by reversing sections of it, each reversal makes a point adjacent
+/// to one of its closest points
+fn two_opt<T>(points: &[(T, T)], path: &mut [usize])
+where
+    T: Float,
+{
+    let num = path.len();
+    if num < 4 {
+        return;
+    }
+    let neighbours = neighbours(points);
+    let mut position = vec![0; num];
+    for (i, index) in path.iter().enumerate() {
+        position[*index] = i;
+    }
+    let point = |path: &[usize], i: usize| points[path[i]];
+    // change of length when reversing the section from `start` to `stop`, the ends of the path have no outer edges
+    let gain = |path: &[usize], start: usize, stop: usize| {
+        let mut delta = T::zero();
+        if start > 0 {
+            delta = delta + distance(point(path, start - 1), point(path, stop))
+                - distance(point(path, start - 1), point(path, start));
+        }
+        if stop + 1 < num {
+            delta = delta + distance(point(path, start), point(path, stop + 1))
+                - distance(point(path, stop), point(path, stop + 1));
+        }
+        delta
+    };
+    let tolerance = T::from(1e-9).unwrap();
+
+    for _ in 0..MAX_PASSES {
+        let mut improved = false;
+        for index in 0..num {
+            for neighbour in &neighbours[index] {
+                let (i, j) = (position[index], position[*neighbour]);
+                let (start, stop) = if j > i + 1 {
+                    (i + 1, j)
+                } else if j + 1 < i {
+                    (j, i - 1)
+                } else {
+                    continue;
+                };
+                if gain(path, start, stop) < -tolerance {
+                    path[start..=stop].reverse();
+                    for k in start..=stop {
+                        position[path[k]] = k;
+                    }
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for TspCurve<'a, T>
+where
+    T: Float + Euclid,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = LineIterator<T>;
+
+    /// Iterates over the segments of the path scaled by the larger image side
+    fn into_iter(self) -> Self::IntoIter {
+        let scale = T::from(self.image.width().max(self.image.height())).unwrap();
+        let segments: Vec<((T, T), (T, T))> = self
+            .lines()
+            .iter()
+            .map(|(start, stop)| {
+                (
+                    (start.0 / scale, start.1 / scale),
+                    (stop.0 / scale, stop.1 / scale),
+                )
+            })
+            .collect();
+        segments.into()
+    }
+}
+
+impl<'a, T> BaseCurve<T> for TspCurve<'a, T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+
+    /// The dots are placed on the image itself and only scaled to the requested size
+    fn fit_to_image(&self, width: usize, height: usize) -> Vec<((T, T), (T, T))> {
+        let scale = (
+            T::from(width).unwrap() / T::from(self.image.width()).unwrap(),
+            T::from(height).unwrap() / T::from(self.image.height()).unwrap(),
+        );
+        self.lines()
+            .iter()
+            .map(|(start, stop)| {
+                (
+                    (start.0 * scale.0, start.1 * scale.1),
+                    (stop.0 * scale.0, stop.1 * scale.1),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,96 @@
+use num::{traits::Euclid, Float};
+use std::marker::PhantomData;
+
+use crate::base_curve::BaseCurve;
+
+/// Z-order (Morton) curve filling a grid of 2^order x 2^order cells, see <https://en.wikipedia.org/wiki/Z-order_curve>
+///
+/// Unlike the Hilbert curve, consecutive cells are not always neighbours, the path jumps between the quadrants.
+#[derive(Clone, Copy, Debug)]
+pub struct ZOrderCurve<T> {
+    order: usize,
+    _marker: PhantomData<T>,
+}
+
+pub struct ZOrderCurveIterator<T>
+where
+    T: Float,
+{
+    z_order_curve: ZOrderCurve<T>,
+    index: usize,
+}
+
+impl<T> ZOrderCurve<T>
+where
+    T: Float,
+{
+    /// Returns the curve of given order, order 0 has no segments and is raised to 1
+    pub fn new(order: usize) -> ZOrderCurve<T> {
+        ZOrderCurve {
+            order: order.max(1),
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Returns the grid cell visited at given index, even bits of the index encode x and odd bits encode y
+    fn position(&self, index: usize) -> (usize, usize) {
+        let (mut x, mut y) = (0, 0);
+        for bit in 0..self.order {
+            x |= ((index >> (2 * bit)) & 1) << bit;
+            y |= ((index >> (2 * bit + 1)) & 1) << bit;
+        }
+        (x, y)
+    }
+}
+
+impl<T> Iterator for ZOrderCurveIterator<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let num_cells = 4usize.pow(self.z_order_curve.order as u32);
+        if self.index + 1 >= num_cells {
+            return None;
+        }
+        let side = T::from(1usize << self.z_order_curve.order).unwrap();
+        let position_start = self.z_order_curve.position(self.index);
+        let position_end = self.z_order_curve.position(self.index + 1);
+        self.index += 1;
+        Some((
+            (
+                T::from(position_start.0).unwrap() / side,
+                T::from(position_start.1).unwrap() / side,
+            ),
+            (
+                T::from(position_end.0).unwrap() / side,
+                T::from(position_end.1).unwrap() / side,
+            ),
+        ))
+    }
+}
+
+impl<T> IntoIterator for ZOrderCurve<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = ZOrderCurveIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ZOrderCurveIterator {
+            z_order_curve: self,
+            index: 0,
+        }
+    }
+}
+
+impl<T> BaseCurve<T> for ZOrderCurve<T>
+where
+    T: Float + Euclid,
+{
+    fn segments(&self) -> Vec<((T, T), (T, T))> {
+        self.into_iter().collect()
+    }
+}
@@ -1,4 +1,3 @@
-use htree::HTree;
 use image::{self, ImageBuffer, Luma, DynamicImage};
 use imageproc::{drawing::{draw_line_segment_mut, draw_filled_rect_mut, draw_antialiased_line_segment}, rect::Rect};
 use line_approximator_lib::{
@@ -39,3 +38,15 @@ fn approximate_image() {
     }
     approximated_image.save("resources/approximated.png").unwrap();
 }
+
+#[test]
+fn approximate_interpolates_brightness() {
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> =
+        ImageBuffer::from_raw(2, 2, vec![0, 100, 200, 255]).unwrap();
+    // a line of one pixel with thickness one is sampled half a pixel below its start, here at (0.25, 0.5)
+    let thickness: f64 = approximate(&image, &(0.25, 0.), &(1.25, 0.), 1.);
+    let top = 0.75 * 0. + 0.25 * 100.;
+    let bottom = 0.75 * 200. + 0.25 * 255.;
+    let brightness = 0.5 * top + 0.5 * bottom;
+    assert!((thickness - (255. - brightness) / 255.).abs() < 1e-9);
+}
@@ -0,0 +1,483 @@
+use image::{ImageBuffer, Luma};
+use line_approximator_lib::{
+    adaptive_hilbert_curve::AdaptiveHilbertCurve,
+    approximator::approximate_image,
+    base_curve::BaseCurve,
+    concentric_curve::{ConcentricCircles, ConcentricSquares},
+    generalized_hilbert_curve::GeneralizedHilbertCurve,
+    gosper_curve::GosperCurve,
+    h_tree_curve::HTreeCurve,
+    line_utils::{connect_points, Line},
+    moore_curve::MooreCurve,
+    parametric_curve::{HarmonographCurve, LissajousCurve, Pendulum},
+    peano_curve::PeanoCurve,
+    scanline_curve::ScanlineCurve,
+    sierpinski_curve::SierpinskiCurve,
+    spiral_curve::SpiralCurve,
+    stipple::Stipple,
+    streamline_curve::{StreamlineCurve, StreamlineDirection},
+    tsp_curve::TspCurve,
+    z_order_curve::ZOrderCurve,
+};
+
+fn assert_continuous(lines: &[Line<f64>]) {
+    for (line, next) in lines.iter().zip(lines.iter().skip(1)) {
+        assert!((line.1 .0 - next.0 .0).abs() < 1e-9 && (line.1 .1 - next.0 .1).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn h_tree_curve() {
+    let lines: Vec<((f64, f64), (f64, f64))> = HTreeCurve::new(4).into_iter().collect();
+    // root segment once, every other segment of the 2^5-2 branches twice as two halves and one full pass
+    assert_eq!(lines.len(), 1 + 3 * 30);
+    assert_continuous(&lines);
+    assert_eq!(lines[0].0, (0.25, 0.5 * std::f64::consts::FRAC_1_SQRT_2));
+    // order 0 is raised to 1
+    assert_eq!(HTreeCurve::<f64>::new(0).segments().len(), 1 + 3 * 2);
+}
+
+#[test]
+fn peano_curve() {
+    let lines: Vec<((f64, f64), (f64, f64))> = PeanoCurve::new(2).into_iter().collect();
+    assert_eq!(lines.len(), 80);
+    assert_continuous(&lines);
+    for (start, stop) in &lines {
+        let step = (start.0 - stop.0).abs() + (start.1 - stop.1).abs();
+        assert!((step - 1. / 9.).abs() < 1e-9);
+    }
+    assert_eq!(lines[0].0, (0., 0.));
+    assert_eq!(lines[79].1, (8. / 9., 8. / 9.));
+    // order 0 is raised to 1
+    assert_eq!(PeanoCurve::<f64>::new(0).segments().len(), 8);
+}
+
+#[test]
+fn moore_curve() {
+    let lines: Vec<((f64, f64), (f64, f64))> = MooreCurve::new(3).into_iter().collect();
+    assert_eq!(lines.len(), 64);
+    assert_continuous(&lines);
+    // closed loop
+    assert_eq!(lines[0].0, lines[63].1);
+    for (start, stop) in &lines {
+        let step = (start.0 - stop.0).abs() + (start.1 - stop.1).abs();
+        assert!((step - 1. / 8.).abs() < 1e-9);
+    }
+    // order 0 is raised to 1
+    assert_eq!(MooreCurve::<f64>::new(0).segments().len(), 4);
+    // the loop stays closed on a non-square image
+    let lines = MooreCurve::<f64>::new(3).fit_to_image(300, 200);
+    assert_eq!(lines.len(), 64);
+    assert_continuous(&lines);
+    assert_eq!(lines[0].0, lines[63].1);
+    for (start, _) in &lines {
+        assert!((0. ..=300.).contains(&start.0) && (0. ..=200.).contains(&start.1));
+    }
+}
+
+#[test]
+fn gosper_curve() {
+    let lines: Vec<((f64, f64), (f64, f64))> = GosperCurve::new(3).into_iter().collect();
+    assert_eq!(lines.len(), 343);
+    assert_continuous(&lines);
+    let step = |line: &((f64, f64), (f64, f64))| {
+        ((line.0 .0 - line.1 .0).powi(2) + (line.0 .1 - line.1 .1).powi(2)).sqrt()
+    };
+    for line in &lines {
+        assert!((step(line) - step(&lines[0])).abs() < 1e-9);
+        for point in [line.0, line.1] {
+            assert!((0. ..=1.).contains(&point.0) && (0. ..=1.).contains(&point.1));
+        }
+    }
+}
+
+#[test]
+fn generalized_hilbert_curve() {
+    for (width, height) in [(12, 5), (7, 9), (16, 16), (1, 4)] {
+        let curve: GeneralizedHilbertCurve<f64> = GeneralizedHilbertCurve::new(width, height);
+        // one continuous path through all cells without leaving the image
+        let lines = curve.fit_to_image(width, height);
+        assert_eq!(lines.len(), width * height - 1);
+        assert_continuous(&lines);
+        let mut cells: Vec<(i64, i64)> = lines
+            .iter()
+            .map(|(start, _)| (start.0.floor() as i64, start.1.floor() as i64))
+            .collect();
+        cells.sort();
+        cells.dedup();
+        assert_eq!(cells.len(), width * height - 1);
+        for (start, stop) in &lines {
+            assert!((start.0 - stop.0).abs() <= 1. && (start.1 - stop.1).abs() <= 1.);
+        }
+    }
+}
+
+#[test]
+fn adaptive_hilbert_curve() {
+    // left half flat, right half checkerboard
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::from_fn(64, 64, |x, y| {
+        if x < 32 || (x + y) % 2 == 0 {
+            Luma([255])
+        } else {
+            Luma([0])
+        }
+    });
+    let uniform: Vec<((f64, f64), (f64, f64))> =
+        AdaptiveHilbertCurve::new(&image, 2, 5, f64::INFINITY).segments();
+    assert_eq!(uniform.len(), 4usize.pow(2) - 1);
+
+    let lines: Vec<((f64, f64), (f64, f64))> =
+        AdaptiveHilbertCurve::new(&image, 2, 5, 10.).segments();
+    assert_continuous(&lines);
+    let mut points: Vec<(f64, f64)> = lines.iter().map(|(start, _)| *start).collect();
+    points.push(lines.last().unwrap().1);
+    let left = points.iter().filter(|point| point.0 < 0.5).count();
+    // flat half keeps the minimal order, detailed half reaches the maximal order
+    assert_eq!(left, 4usize.pow(2) / 2);
+    assert_eq!(points.len() - left, 4usize.pow(5) / 2);
+
+    // on a non-square image all cells stay within the image and the curve stays one path
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> =
+        ImageBuffer::from_fn(96, 32, |x, y| Luma([((x * y) % 256) as u8]));
+    let lines: Vec<((f64, f64), (f64, f64))> =
+        AdaptiveHilbertCurve::new(&image, 2, 4, 10.).fit_to_image(96, 32);
+    assert!(lines.len() >= 4usize.pow(2) - 1);
+    assert_continuous(&lines);
+    for (start, stop) in &lines {
+        for point in [start, stop] {
+            assert!((0. ..=96.).contains(&point.0) && (0. ..=32.).contains(&point.1));
+        }
+    }
+}
+
+#[test]
+fn spiral_curve() {
+    let center = (0.25, 0.5);
+    let distance = |point: &(f64, f64)| (point.0 - center.0).hypot(point.1 - center.1);
+    let lines: Vec<((f64, f64), (f64, f64))> = SpiralCurve::new(10, center, true).segments();
+    assert_eq!(lines[0].0, center);
+    // the spiral is one path up to the closest border
+    let inner: Vec<_> = lines
+        .iter()
+        .take_while(|(_, stop)| distance(stop) < 0.25)
+        .copied()
+        .collect();
+    assert!(inner.len() > 64);
+    assert_continuous(&inner);
+    // distance to center grows monotonically
+    for (start, stop) in &inner {
+        assert!(distance(stop) > distance(start));
+    }
+
+    // turns are clipped to the image, the last one reaches the farthest corner
+    let spacing = 0.75f64.hypot(0.5) / 10.;
+    let curve = SpiralCurve::new(10, center, true);
+    for (lines, width, height) in [
+        (curve.segments(), 1., 1.),
+        (curve.fit_to_image(200, 100), 200., 100.),
+    ] {
+        for (start, stop) in &lines {
+            for point in [start, stop] {
+                assert!((0. ..=width).contains(&point.0) && (0. ..=height).contains(&point.1));
+            }
+        }
+        for corner in [(0., 0.), (width, 0.), (0., height), (width, height)] {
+            assert!(lines.iter().any(|(start, _)| {
+                ((start.0 - corner.0) / width).hypot((start.1 - corner.1) / height) < spacing
+            }));
+        }
+    }
+    // a center on the border gives a spiral around it
+    let lines: Vec<((f64, f64), (f64, f64))> =
+        SpiralCurve::new(10, (-0.5, 0.5), true).fit_to_image(200, 100);
+    assert_eq!(lines[0].0, (0., 50.));
+    assert!(lines.iter().any(|(start, _)| start.0 > 190.));
+}
+
+#[test]
+fn scanline_curve() {
+    let lines: Vec<((f64, f64), (f64, f64))> = ScanlineCurve::new(0.1, 0.).segments();
+    // 10 rows joined by 9 connections
+    assert_eq!(lines.len(), 19);
+    assert_continuous(&lines);
+    let close = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9;
+    assert!(close(lines[0].0, (0., 0.05)) && close(lines[0].1, (1., 0.05)));
+    assert!(close(lines[2].0, (1., 0.15)) && close(lines[2].1, (0., 0.15)));
+
+    let lines: Vec<((f64, f64), (f64, f64))> =
+        ScanlineCurve::new(0.05, 30f64.to_radians()).fit_to_image(300, 200);
+    assert_continuous(&lines);
+    for (start, stop) in &lines {
+        for point in [start, stop] {
+            assert!((-1e-9..=300. + 1e-9).contains(&point.0));
+            assert!((-1e-9..=200. + 1e-9).contains(&point.1));
+        }
+    }
+
+    // a spacing that is not positive falls back to a single line through the center
+    for spacing in [0., -0.1, f64::NAN] {
+        let lines: Vec<((f64, f64), (f64, f64))> =
+            ScanlineCurve::new(spacing, 0.).fit_to_image(300, 200);
+        assert_eq!(lines, vec![((0., 100.), (300., 100.))]);
+    }
+}
+
+#[test]
+fn concentric_curves() {
+    let center = (0.5, 0.5);
+    let circles: Vec<((f64, f64), (f64, f64))> = ConcentricCircles::new(4, center).segments();
+    let squares: Vec<((f64, f64), (f64, f64))> = ConcentricSquares::new(4, center).segments();
+    // the outermost square runs along the border, the outer circles are clipped at it
+    assert_continuous(&squares);
+    let spacing = 0.5f64.hypot(0.5) / 4.;
+    let distance = |point: &(f64, f64)| (point.0 - center.0).hypot(point.1 - center.1);
+    let inner: Vec<_> = circles
+        .iter()
+        .take_while(|(_, stop)| distance(stop) <= 0.5)
+        .copied()
+        .collect();
+    assert_continuous(&inner);
+    assert_eq!(circles[0].0, (0.5 + spacing, 0.5));
+    assert_eq!(squares[0].0, (0.625, 0.5));
+    // circles keep their radius, squares their chebyshev distance to the center
+    for (start, _) in &inner {
+        let radius = distance(start) / spacing;
+        assert!((radius - radius.round()).abs() < 1e-9);
+    }
+    for (start, _) in &squares {
+        let half_side = (start.0 - center.0).abs().max((start.1 - center.1).abs()) / 0.125;
+        assert!((half_side - half_side.round()).abs() < 1e-9);
+    }
+    // off-center rings are clipped to the image and cover it up to the corners
+    for center in [(0.3, 0.6), (-1., 0.5)] {
+        for lines in [
+            ConcentricCircles::new(5, center).fit_to_image(200, 100),
+            ConcentricSquares::new(5, center).fit_to_image(200, 100),
+        ] {
+            for (start, stop) in &lines {
+                for point in [start, stop] {
+                    assert!((0. ..=200.).contains(&point.0) && (0. ..=100.).contains(&point.1));
+                }
+            }
+            for corner in [(0f64, 0f64), (200., 0.), (0., 100.), (200., 100.)] {
+                assert!(lines.iter().any(|(start, stop)| {
+                    [start, stop]
+                        .iter()
+                        .any(|point| (point.0 - corner.0).hypot(point.1 - corner.1) < 50.)
+                }));
+            }
+        }
+    }
+}
+
+#[test]
+fn sierpinski_curve() {
+    let lines: Vec<((f64, f64), (f64, f64))> = SierpinskiCurve::new(2).into_iter().collect();
+    assert_eq!(lines.len(), 64);
+    assert_continuous(&lines);
+    assert_eq!(lines[0].0, lines[63].1);
+    let step =
+        |line: &((f64, f64), (f64, f64))| (line.0 .0 - line.1 .0).hypot(line.0 .1 - line.1 .1);
+    for line in &lines {
+        assert!((step(line) - step(&lines[0])).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn z_order_curve() {
+    let lines: Vec<((f64, f64), (f64, f64))> = ZOrderCurve::new(2).into_iter().collect();
+    assert_eq!(lines.len(), 15);
+    assert_continuous(&lines);
+    assert_eq!(lines[0], ((0., 0.), (0.25, 0.)));
+    assert_eq!(lines[1], ((0.25, 0.), (0., 0.25)));
+    // jump from the first to the second quadrant
+    assert_eq!(lines[3], ((0.25, 0.25), (0.5, 0.)));
+    // order 0 is raised to 1
+    assert_eq!(ZOrderCurve::<f64>::new(0).segments().len(), 3);
+}
+
+#[test]
+fn parametric_curves() {
+    let lines: Vec<((f64, f64), (f64, f64))> =
+        LissajousCurve::new((3., 2.), std::f64::consts::FRAC_PI_2, 200).segments();
+    assert_eq!(lines.len(), 200);
+    assert_continuous(&lines);
+    // integer frequencies close the figure
+    let (first, last) = (lines[0].0, lines[199].1);
+    assert!((first.0 - last.0).abs() < 1e-9 && (first.1 - last.1).abs() < 1e-9);
+    assert!((first.0 - 1.).abs() < 1e-9 && (first.1 - 0.5).abs() < 1e-9);
+
+    let pendulum = |frequency| Pendulum::new(1., frequency, 0., 0.1);
+    let curve = HarmonographCurve::new(vec![pendulum(3.)], vec![pendulum(2.)], 50., 1000);
+    let lines: Vec<((f64, f64), (f64, f64))> = curve.segments();
+    assert_eq!(lines.len(), 1000);
+    assert_continuous(&lines);
+    // damped pendulums come to rest in the center
+    let end = lines[999].1;
+    assert!((end.0 - 0.5).abs() < 0.01 && (end.1 - 0.5).abs() < 0.01);
+
+    // too few segments are raised to 16 per period of the fastest pendulum
+    let curve = HarmonographCurve::new(vec![pendulum(3.)], vec![pendulum(2.)], 230., 2);
+    let lines: Vec<((f64, f64), (f64, f64))> = curve.segments();
+    assert_eq!(
+        lines.len(),
+        (230. * 3. / std::f64::consts::TAU * 16.).ceil() as usize
+    );
+    let curve = HarmonographCurve::new(vec![pendulum(3.)], vec![pendulum(2.)], 50., 1000);
+
+    let lines = curve.fit_to_image(300, 200);
+    for (start, stop) in &lines {
+        for point in [start, stop] {
+            assert!((-1e-9..=300. + 1e-9).contains(&point.0));
+            assert!((-1e-9..=200. + 1e-9).contains(&point.1));
+        }
+    }
+
+    // figures without extent along an axis are centered on it
+    let lines = LissajousCurve::new((3., 0.), 0., 200).fit_to_image(300, 200);
+    assert!(lines
+        .iter()
+        .all(|(start, stop)| start.1 == 100. && stop.1 == 100.));
+    let curve = HarmonographCurve::new(vec![pendulum(3.)], vec![pendulum(0.)], 50., 1000);
+    let lines = curve.fit_to_image(300, 200);
+    assert!(lines
+        .iter()
+        .all(|(start, stop)| start.1 == 100. && stop.1 == 100.));
+    assert!(lines.iter().any(|(start, stop)| start.0 != stop.0));
+}
+
+#[test]
+fn streamline_curve() {
+    // brightness increases to the right
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> =
+        ImageBuffer::from_fn(64, 64, |x, _| Luma([(x * 4) as u8]));
+    let contours: Vec<Vec<(f64, f64)>> =
+        StreamlineCurve::new(&image, 0.125, StreamlineDirection::Contour).polylines();
+    let gradients: Vec<Vec<(f64, f64)>> =
+        StreamlineCurve::new(&image, 0.125, StreamlineDirection::Gradient).polylines();
+    assert!(contours.len() > 1 && gradients.len() > 1);
+    // contours run vertically through the whole image, gradients horizontally
+    for polyline in &contours {
+        assert!(polyline
+            .iter()
+            .all(|point| (point.0 - polyline[0].0).abs() < 1e-3));
+        assert!((polyline[0].1 - polyline.last().unwrap().1).abs() > 60.);
+    }
+    for polyline in &gradients {
+        assert!(polyline
+            .iter()
+            .all(|point| (point.1 - polyline[0].1).abs() < 1e-3));
+    }
+    // streamlines keep half the separation apart
+    for (i, polyline) in contours.iter().enumerate() {
+        for other in &contours[i + 1..] {
+            assert!((polyline[0].0 - other[0].0).abs() >= 4.);
+        }
+    }
+}
+
+#[test]
+fn tsp_curve() {
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> =
+        ImageBuffer::from_fn(40, 20, |x, y| Luma([(x * 6 + y) as u8]));
+    let curve: TspCurve<f64> = TspCurve::new(&image, 100, 5, 3);
+    let tour = curve.tour();
+    // every dot visited once
+    let mut points: Vec<(f64, f64)> = Stipple::new(&image, 100, 5, 3).points();
+    let mut visited = tour.clone();
+    let by_position = |a: &(f64, f64), b: &(f64, f64)| a.partial_cmp(b).unwrap();
+    points.sort_by(by_position);
+    visited.sort_by(by_position);
+    assert_eq!(points, visited);
+    assert_eq!(tour, TspCurve::new(&image, 100, 5, 3).tour());
+
+    let lines: Vec<((f64, f64), (f64, f64))> = curve.segments();
+    assert_eq!(lines.len(), 99);
+    assert_continuous(&lines);
+    assert_eq!(lines[0].0, (tour[0].0 / 40., tour[0].1 / 40.));
+
+    // 2-opt shortens the path found by always moving to the closest unvisited dot
+    let tour_length = |tour: &[(f64, f64)]| -> f64 {
+        tour.iter()
+            .zip(tour.iter().skip(1))
+            .map(|(a, b)| (a.0 - b.0).hypot(a.1 - b.1))
+            .sum()
+    };
+    let mut unvisited: Vec<(f64, f64)> = Stipple::new(&image, 100, 5, 3).points();
+    let mut nearest_neighbour = vec![unvisited.remove(0)];
+    while !unvisited.is_empty() {
+        let current = *nearest_neighbour.last().unwrap();
+        let distance = |point: &(f64, f64)| (point.0 - current.0).hypot(point.1 - current.1);
+        let next = (0..unvisited.len())
+            .min_by(|a, b| {
+                distance(&unvisited[*a])
+                    .partial_cmp(&distance(&unvisited[*b]))
+                    .unwrap()
+            })
+            .unwrap();
+        nearest_neighbour.push(unvisited.remove(next));
+    }
+    // the nearest neighbour path crosses itself, so reversing the section between crossing edges shortens it
+    let side = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+        ((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)).signum()
+    };
+    let crosses = |(a, b): Line<f64>, (c, d): Line<f64>| {
+        side(a, b, c) * side(a, b, d) < 0. && side(c, d, a) * side(c, d, b) < 0.
+    };
+    let edges = connect_points(&nearest_neighbour);
+    assert!(edges
+        .iter()
+        .enumerate()
+        .any(|(i, edge)| edges[i + 1..].iter().any(|other| crosses(*edge, *other))));
+    assert!(tour_length(&tour) < tour_length(&nearest_neighbour));
+
+    // a white image has no dots and gives an empty curve, which is approximated by no lines
+    let white: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::from_pixel(40, 20, Luma([255]));
+    let curve: TspCurve<f32> = TspCurve::new(&white, 100, 5, 3);
+    assert!(curve.segments().is_empty());
+    assert!(curve.fit_to_image(40, 20).is_empty());
+    assert!(approximate_image(&white, &curve, 1., false).is_empty());
+    // an image of uniform brightness is toured like a dark one
+    let flat: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::from_pixel(40, 20, Luma([128]));
+    let curve: TspCurve<f32> = TspCurve::new(&flat, 100, 5, 3);
+    assert_eq!(curve.segments().len(), 99);
+}
+
+/// Diagonal zigzag in unit-square coordinates
+struct ZigZag;
+
+impl BaseCurve<f32> for ZigZag {
+    fn segments(&self) -> Vec<((f32, f32), (f32, f32))> {
+        (0..10)
+            .map(|i| {
+                let x = i as f32 / 10.;
+                ((x, (i % 2) as f32), (x + 0.1, ((i + 1) % 2) as f32))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn approximate_image_with_custom_curve() {
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> =
+        ImageBuffer::from_fn(32, 32, |x, _| Luma([(x * 8) as u8]));
+    let lines = approximate_image(&image, &ZigZag, 1., false);
+    assert!(!lines.is_empty());
+    for (start, stop) in lines {
+        assert!(
+            start.0.is_finite() && start.1.is_finite() && stop.0.is_finite() && stop.1.is_finite()
+        );
+    }
+    // empty and degenerate paths are approximated by no lines
+    assert!(approximate_image(&image, &Vec::<Line<f32>>::new(), 1., false).is_empty());
+    let point: Vec<Line<f32>> = vec![((0.5, 0.5), (0.5, 0.5))];
+    assert!(point
+        .fit_to_image(32, 32)
+        .iter()
+        .all(|line| *line == ((16., 16.), (16., 16.))));
+    assert!(approximate_image(&image, &point, 1., false).is_empty());
+    // a path along a single axis
+    let row: Vec<Line<f32>> = vec![((0., 0.5), (1., 0.5))];
+    assert_eq!(row.fit_to_image(32, 32), vec![((0., 16.), (32., 16.))]);
+    assert!(!approximate_image(&image, &row, 1., false).is_empty());
+}
@@ -0,0 +1,73 @@
+use line_approximator_lib::{
+    gcode::{to_gcode, GcodeOptions, PenControl},
+    hpgl::{to_hpgl, HpglOptions, PageSize},
+    line_utils::join_lines,
+    svg::{to_svg, SvgOptions},
+};
+
+#[test]
+fn join_connected_lines() {
+    let lines: Vec<((f32, f32), (f32, f32))> = vec![
+        ((0., 0.), (1., 0.)),
+        ((1., 0.), (1., 1.)),
+        ((2., 2.), (3., 3.)),
+    ];
+    let polylines = join_lines(&lines, 1e-3);
+    assert_eq!(polylines.len(), 2);
+    assert_eq!(polylines[0], vec![(0., 0.), (1., 0.), (1., 1.)]);
+    assert_eq!(polylines[1], vec![(2., 2.), (3., 3.)]);
+}
+
+#[test]
+fn export_svg() {
+    let lines: Vec<((f32, f32), (f32, f32))> = vec![
+        ((0., 0.), (10., 0.)),
+        ((10., 0.), (10., 5.)),
+        ((20., 20.), (30., 30.)),
+    ];
+    let mut options = SvgOptions::with_width(100., 40, 20);
+    options.stroke_width = 0.5;
+    let svg = to_svg(&lines, 40, 20, &options);
+    assert!(svg.contains(r#"width="100mm" height="50mm" viewBox="0 0 40 20""#));
+    assert!(svg.contains(r#"stroke-width="0.5""#));
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert!(svg.contains(r#"points="0.000,0.000 10.000,0.000 10.000,5.000""#));
+}
+
+#[test]
+fn export_gcode() {
+    let lines: Vec<((f32, f32), (f32, f32))> = vec![
+        ((0., 0.), (10., 0.)),
+        ((10., 0.), (10., 5.)),
+        ((20., 20.), (30., 30.)),
+    ];
+    let mut options = GcodeOptions::new(0.5);
+    options.pen = PenControl::Servo {
+        power: 1000,
+        dwell: 0.1,
+    };
+    let gcode = to_gcode(&lines, 40, &options);
+    let commands: Vec<&str> = gcode.lines().collect();
+    assert_eq!(&commands[..2], &["G21", "G90"]);
+    assert_eq!(gcode.matches("M3 S1000").count(), 2);
+    assert_eq!(gcode.matches("M5").count(), 3);
+    assert!(gcode.contains("G0 X0.000 Y20.000\nM3 S1000\nG4 P0.100\nG1 X5.000 Y20.000 F1000.0\nG1 X5.000 Y17.500\n"));
+    assert_eq!(commands.last(), Some(&"M2"));
+}
+
+#[test]
+fn export_hpgl() {
+    let lines: Vec<((f32, f32), (f32, f32))> = vec![
+        ((0., 0.), (10., 0.)),
+        ((10., 0.), (10., 5.)),
+        ((20., 20.), (30., 30.)),
+    ];
+    let mut options = HpglOptions::new(PageSize::Custom(60., 40.), 2);
+    options.margin_mm = 0.;
+    let hpgl = to_hpgl(&lines, 60, 40, &options);
+    // one pixel is scaled to one mm -> 40 plotter units
+    assert_eq!(
+        hpgl,
+        "IN;\nSP2;\nPA;\nPU0,1600;\nPD400,1600,400,1400;\nPU800,800;\nPD1200,400;\nPU;\nSP0;\n"
+    );
+}
@@ -0,0 +1,43 @@
+use image::{ImageBuffer, Luma};
+use line_approximator_lib::{
+    hatching::{hatch, HatchLayer},
+    line_utils::Line,
+};
+
+#[test]
+fn hatch_layers() {
+    let layers: Vec<HatchLayer<f64>> = HatchLayer::layers(4);
+    let thresholds: Vec<f64> = layers.iter().map(|layer| layer.threshold).collect();
+    assert_eq!(thresholds, vec![204., 153., 102., 51.]);
+    assert!((layers[3].angle - 135f64.to_radians()).abs() < 1e-9);
+    assert_eq!(HatchLayer::<f64>::layers(9).len(), 4);
+}
+
+#[test]
+fn hatch_image() {
+    // black, gray and white thirds
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::from_fn(90, 30, |x, _| match x {
+        0..=29 => Luma([0]),
+        30..=59 => Luma([128]),
+        _ => Luma([255]),
+    });
+    let layers: Vec<Vec<Line<f64>>> = hatch(&image, &HatchLayer::layers(4), 0.1);
+    assert_eq!(layers.len(), 4);
+    // the two lighter layers cover the gray third, the darker ones only the black third
+    for (layer, right_end) in layers.iter().zip([60., 60., 30., 30.]) {
+        assert!(!layer.is_empty());
+        for (start, stop) in layer {
+            assert!(start.0.max(stop.0) <= right_end + 1.5);
+        }
+        assert!(layer
+            .iter()
+            .any(|(start, stop)| start.0.max(stop.0) >= right_end - 9.));
+    }
+    // horizontal first layer, vertical third layer
+    assert!(layers[0]
+        .iter()
+        .all(|(start, stop)| (start.1 - stop.1).abs() < 1e-9));
+    assert!(layers[2]
+        .iter()
+        .all(|(start, stop)| (start.0 - stop.0).abs() < 1e-9));
+}
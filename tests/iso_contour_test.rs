@@ -0,0 +1,39 @@
+use image::{ImageBuffer, Luma};
+use line_approximator_lib::{
+    iso_contour::{iso_contours, iso_levels},
+    line_utils::Line,
+};
+
+#[test]
+fn iso_levels_spacing() {
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> =
+        ImageBuffer::from_fn(4, 1, |x, _| Luma([(50 + x * 50) as u8]));
+    let levels: Vec<f64> = iso_levels(&image, 4, 1.);
+    assert_eq!(levels, vec![80., 110., 140., 170.]);
+    // darker levels closer together
+    let levels: Vec<f64> = iso_levels(&image, 4, 2.);
+    assert!(levels[1] - levels[0] < levels[3] - levels[2]);
+    assert!(levels.iter().all(|level| (50. ..200.).contains(level)));
+}
+
+#[test]
+fn iso_contours_of_cone() {
+    // brightness grows with the distance to the center
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::from_fn(64, 64, |x, y| {
+        Luma([((x as f64 - 32.).hypot(y as f64 - 32.) * 4.) as u8])
+    });
+    let contours: Vec<Vec<Line<f64>>> = iso_contours(&image, &[40., 80.], 1.);
+    assert_eq!(contours.len(), 2);
+    for (contour, radius) in contours.iter().zip([10., 20.]) {
+        assert!(!contour.is_empty());
+        // closed circle traced in one go
+        for (line, next) in contour.iter().zip(contour.iter().skip(1)) {
+            assert_eq!(line.1, next.0);
+        }
+        assert_eq!(contour[0].0, contour.last().unwrap().1);
+        for (start, _) in contour {
+            let distance = (start.0 - 32.).hypot(start.1 - 32.);
+            assert!((distance - radius).abs() < 1., "{} {}", distance, radius);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use line_approximator_lib::line_utils::{clip, connect_points, smooth_corners, LineIterator};
+
+#[test]
+fn smooth_corners_keeps_disconnected_lines() {
+    let lines = vec![((0., 0.), (4., 0.)), ((6., 2.), (6., 6.))];
+    assert_eq!(smooth_corners(&lines), lines);
+    // a corner followed by a gap is rounded and the line before the gap kept
+    let lines = vec![
+        ((0., 0.), (4., 0.)),
+        ((4., 0.), (4., 4.)),
+        ((8., 8.), (8., 12.)),
+    ];
+    let smoothed = smooth_corners(&lines);
+    assert!(smoothed.len() > lines.len());
+    assert!(smoothed.iter().any(|line| line.1 == (4., 4.)));
+    assert_eq!(smoothed.last(), Some(&((8., 8.), (8., 12.))));
+}
+
+#[test]
+fn smooth_corners_keeps_ends() {
+    // the line after the last corner is kept up to its end
+    let lines = vec![((0., 0.), (4., 0.)), ((4., 0.), (4., 4.))];
+    let smoothed = smooth_corners(&lines);
+    assert_eq!(smoothed[0].0, (0., 0.));
+    assert_eq!(smoothed.last(), Some(&((4., 2.), (4., 4.))));
+    for (line, next) in smoothed.iter().zip(smoothed.iter().skip(1)) {
+        assert_eq!(line.1, next.0);
+    }
+    assert!(smooth_corners::<f64>(&Vec::new()).is_empty());
+}
+
+#[test]
+fn clip_cuts_lines_at_the_border() {
+    let mut lines = vec![
+        ((2., 2.), (8., 4.)),
+        ((-5., 5.), (5., 5.)),
+        ((5., 5.), (5., 15.)),
+        ((-5., -5.), (15., 15.)),
+        ((12., 0.), (12., 10.)),
+        ((-2., 4.), (4., -2.)),
+    ];
+    clip(&mut lines, 10, 10);
+    assert_eq!(
+        lines,
+        vec![
+            ((2., 2.), (8., 4.)),
+            ((0., 5.), (5., 5.)),
+            ((5., 5.), (5., 10.)),
+            ((0., 0.), (10., 10.)),
+            ((0., 2.), (2., 0.)),
+        ]
+    );
+}
+
+#[test]
+fn connect_points_into_lines() {
+    let lines = connect_points(&[(0., 0.), (1., 0.), (1., 1.)]);
+    assert_eq!(lines, vec![((0., 0.), (1., 0.)), ((1., 0.), (1., 1.))]);
+    assert!(connect_points::<f64>(&[(0., 0.)]).is_empty());
+    assert_eq!(LineIterator::from(lines.clone()).collect::<Vec<_>>(), lines);
+}
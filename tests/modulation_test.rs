@@ -0,0 +1,244 @@
+use line_approximator_lib::{
+    base_curve::BaseCurve,
+    hilbert_curve::HilbertCurve,
+    line_utils::{length, partition_line, thicken_lines_sin, Line},
+    modulation::{
+        frequency_modulate_lines, loop_lines, modulate_lines, smooth_modulate_lines, Modulation,
+        Waveform,
+    },
+};
+
+fn straight_line(length: f64) -> Vec<Line<f64>> {
+    partition_line(&(0., 0.), &(length, 0.), 1.)
+}
+
+#[test]
+fn waveform_values() {
+    let sine = Modulation::new(Waveform::Sine, 1.);
+    for phase in [0.1, 0.3, 0.6, 0.9] {
+        let expected = (2. * std::f64::consts::PI * phase).sin();
+        assert!((sine.value(phase, false) - expected).abs() < 1e-9);
+    }
+    let square = Modulation::new(Waveform::Square, 1.).with_duty_cycle(0.25);
+    assert_eq!(square.value(0.2, false), 1.);
+    assert_eq!(square.value(0.3, false), -1.);
+    // jumps
+    assert_eq!(square.value(0.25, true), 1.);
+    assert_eq!(square.value(0.25, false), -1.);
+    assert_eq!(square.value(0., true), -1.);
+    let triangle = Modulation::new(Waveform::Triangle, 1.).with_duty_cycle(0.25);
+    assert_eq!(triangle.value(0., false), -1.);
+    assert_eq!(triangle.value(0.25, false), 1.);
+    assert_eq!(triangle.value(0.625, false), 0.);
+    let sawtooth = Modulation::new(Waveform::Sawtooth, 1.);
+    assert_eq!(sawtooth.value(0.75, false), 0.5);
+    assert_eq!(sawtooth.value(0., true), 1.);
+}
+
+#[test]
+fn sine_modulation_width() {
+    // the default renderer swings out as far as thicken_lines_sin, by the thickness to both sides
+    let lines = straight_line(40.);
+    let thicknesses = vec![3.; lines.len()];
+    let modulation = Modulation::new(Waveform::Sine, 1.);
+    let width = |stroke: &[Line<f64>]| {
+        stroke
+            .iter()
+            .flat_map(|(start, stop)| [start.1, stop.1])
+            .fold(0., |max: f64, y| max.max(y.abs()))
+    };
+    let stroke = modulate_lines(&lines, &thicknesses, &modulation, false);
+    assert!((width(&stroke) - 3.).abs() < 1e-2);
+    let sin = thicken_lines_sin(&lines, &thicknesses, 1., false);
+    assert!((width(&stroke) - width(&sin)).abs() < 1e-2);
+}
+
+#[test]
+fn negative_omega() {
+    let lines = straight_line(20.);
+    let thicknesses = vec![2.; lines.len()];
+    let stroke = modulate_lines(
+        &lines,
+        &thicknesses,
+        &Modulation::new(Waveform::Triangle, 1.),
+        false,
+    );
+    let negative = modulate_lines(
+        &lines,
+        &thicknesses,
+        &Modulation::new(Waveform::Triangle, -1.),
+        false,
+    );
+    assert_eq!(stroke, negative);
+}
+
+#[test]
+fn square_modulation() {
+    let lines = straight_line(20.);
+    let thicknesses = vec![1.; lines.len()];
+    // period of 4 pixels
+    let modulation = Modulation::new(Waveform::Square, std::f64::consts::PI / 2.);
+    let stroke = modulate_lines(&lines, &thicknesses, &modulation, false);
+    for (line, next) in stroke.iter().zip(stroke.iter().skip(1)) {
+        assert_eq!(line.1, next.0);
+    }
+    assert_eq!(stroke[0].0, (0., 1.));
+    assert_eq!(stroke.last().unwrap().1, (20., -1.));
+    // the stroke runs along both sides of the line and crosses it perpendicularly at the jumps
+    for (start, stop) in &stroke {
+        if start.0 == stop.0 {
+            assert!((start.0 / 2. - (start.0 / 2.).round()).abs() < 1e-9);
+            assert!((start.1 - stop.1).abs() == 2.);
+        } else {
+            assert!(start.1.abs() == 1. && start.1 == stop.1);
+        }
+    }
+    let jumps = stroke
+        .iter()
+        .filter(|(start, stop)| start.0 == stop.0)
+        .count();
+    assert_eq!(jumps, 9);
+}
+
+#[test]
+fn modulation_drops_bright_lines() {
+    let lines = straight_line(10.);
+    let thicknesses: Vec<f64> = (0..10).map(|i| if i == 5 { 0.5 } else { 2. }).collect();
+    let modulation = Modulation::new(Waveform::Triangle, 1.);
+    let stroke = modulate_lines(&lines, &thicknesses, &modulation, true);
+    let gaps = stroke
+        .iter()
+        .zip(stroke.iter().skip(1))
+        .filter(|(line, next)| line.1 != next.0)
+        .count();
+    assert_eq!(gaps, 1);
+    // nothing is drawn along the dropped pixel
+    assert!(stroke
+        .iter()
+        .all(|(start, stop)| start.0.max(stop.0) <= 5. || start.0.min(stop.0) >= 6.));
+}
+
+#[test]
+fn frequency_modulation() {
+    let lines = straight_line(40.);
+    // half darkness on the first half, full darkness on the second one
+    let thicknesses: Vec<f64> = (0..40).map(|i| if i < 20 { 2. } else { 4. }).collect();
+    // period of 2 pixels at full darkness
+    let modulation = Modulation::new(Waveform::Sine, std::f64::consts::PI);
+    let stroke = frequency_modulate_lines(&lines, &thicknesses, 4., &modulation, false);
+    for (line, next) in stroke.iter().zip(stroke.iter().skip(1)) {
+        assert_eq!(line.1, next.0);
+    }
+    // constant amplitude, the phase runs on continuously where the frequency changes
+    for (start, stop) in &stroke {
+        assert!(start.1.abs() <= 2. + 1e-9);
+        assert!((start.1 - stop.1).abs() <= std::f64::consts::FRAC_PI_4 + 1e-9);
+    }
+    let crossings = |from: f64, to: f64| {
+        let signs: Vec<f64> = stroke
+            .iter()
+            .filter(|(start, _)| (from..to).contains(&start.0) && start.1.abs() > 1e-6)
+            .map(|(start, _)| start.1.signum())
+            .collect();
+        signs.windows(2).filter(|pair| pair[0] != pair[1]).count()
+    };
+    assert!((9..=10).contains(&crossings(0., 20.)));
+    assert!((19..=20).contains(&crossings(20., 40.)));
+}
+
+#[test]
+fn loop_modulation() {
+    let lines = straight_line(20.);
+    let thicknesses: Vec<f64> = (0..20).map(|i| if i < 10 { 4. } else { 0.5 }).collect();
+    // period of 4 pixels
+    let omega = std::f64::consts::PI / 2.;
+    let stroke = loop_lines(&lines, &thicknesses, omega, false);
+    for (line, next) in stroke.iter().zip(stroke.iter().skip(1)) {
+        assert_eq!(line.1, next.0);
+    }
+    assert_eq!(stroke[0].0, (2., 0.));
+    // the pen stays on circles of half the thickness around the lines and moves back along the path in loops
+    for (start, _) in &stroke {
+        let radius = if start.0 < 12. { 2. } else { 0.25 };
+        assert!(start.1.abs() <= radius + 1e-9);
+    }
+    assert!(stroke
+        .iter()
+        .filter(|(start, _)| start.0 < 10.)
+        .any(|(start, stop)| stop.0 < start.0));
+    assert!(stroke
+        .iter()
+        .filter(|(start, _)| start.0 > 11.)
+        .all(|(start, stop)| stop.0 > start.0));
+    // bright lines are left out
+    let stroke = loop_lines(&lines, &thicknesses, omega, true);
+    assert!(stroke
+        .iter()
+        .all(|(start, stop)| start.0.max(stop.0) <= 12. + 1e-9));
+}
+
+#[test]
+fn smooth_modulation_through_corners() {
+    // hilbert curve with sharp corners every 8 pixels
+    let lines: Vec<Line<f64>> = HilbertCurve::new(3)
+        .fit_to_image(64, 64)
+        .iter()
+        .flat_map(|(start, stop)| partition_line(start, stop, 1.))
+        .collect();
+    let modulation = Modulation::new(Waveform::Sine, 1.);
+    let longest = |stroke: &[Line<f64>]| {
+        stroke
+            .iter()
+            .map(|(start, stop)| length(start, stop))
+            .fold(0., f64::max)
+    };
+    // with constant thickness only the corners can make the stroke jump
+    let thicknesses = vec![3.; lines.len()];
+    let stroke = smooth_modulate_lines(&lines, &thicknesses, &modulation, false);
+    for (line, next) in stroke.iter().zip(stroke.iter().skip(1)) {
+        assert_eq!(line.1, next.0);
+    }
+    assert!(longest(&stroke) <= 2.);
+    // offsetting perpendicular to each line jumps at the corners
+    let stroke = modulate_lines(&lines, &thicknesses, &modulation, false);
+    assert!(longest(&stroke) > 2.);
+
+    // changes of the thickness are interpolated as well
+    let thicknesses: Vec<f64> = (0..lines.len()).map(|i| 2. + (i % 5) as f64).collect();
+    let stroke = smooth_modulate_lines(&lines, &thicknesses, &modulation, false);
+    assert!(longest(&stroke) <= 2.);
+}
+
+#[test]
+fn dropped_lines_keep_phase() {
+    let lines = straight_line(20.);
+    let thicknesses: Vec<f64> = (0..20).map(|i| if i == 5 { 0.5 } else { 2. }).collect();
+    let modulation = Modulation::new(Waveform::Sine, 1.);
+    // the phase runs on along the dropped pixel, so the stroke after it matches the one drawn without dropping
+    let after = |stroke: Vec<Line<f64>>| -> Vec<Line<f64>> {
+        stroke
+            .into_iter()
+            .filter(|(start, _)| start.0 >= 6.)
+            .collect()
+    };
+    assert_eq!(
+        after(modulate_lines(&lines, &thicknesses, &modulation, true)),
+        after(modulate_lines(&lines, &thicknesses, &modulation, false))
+    );
+    assert_eq!(
+        after(frequency_modulate_lines(
+            &lines,
+            &thicknesses,
+            2.,
+            &modulation,
+            true
+        )),
+        after(frequency_modulate_lines(
+            &lines,
+            &thicknesses,
+            2.,
+            &modulation,
+            false
+        ))
+    );
+}
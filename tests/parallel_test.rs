@@ -0,0 +1,74 @@
+use line_approximator_lib::line_utils::{
+    join_lines, length, partition_line, thicken_lines_parallel,
+};
+
+#[test]
+fn parallel_copies() {
+    let lines = partition_line(&(0., 0.), &(10., 0.), 1.);
+    let thicknesses = vec![3.; lines.len()];
+    let copies = thicken_lines_parallel(&lines, &thicknesses, 1., false);
+    for offset in [-1., 0., 1.] {
+        let copy: Vec<_> = copies
+            .iter()
+            .filter(|(start, stop)| start.1 == offset && stop.1 == offset)
+            .collect();
+        assert_eq!(
+            copy.iter()
+                .map(|(start, stop)| length(start, stop))
+                .sum::<f64>(),
+            10.
+        );
+    }
+    // the first copy follows the path, every other copy runs backwards, so the copies form short moves
+    assert_eq!(copies[0].0, (0., 0.));
+    let side = copies.iter().find(|(start, _)| start.1 == 1.).unwrap();
+    assert!(side.1 .0 < side.0 .0);
+    // bright lines
+    assert_eq!(
+        thicken_lines_parallel(&lines, &[0.5; 10], 1., false).len(),
+        10
+    );
+    assert!(thicken_lines_parallel(&lines, &[0.5; 10], 1., true).is_empty());
+}
+
+#[test]
+fn parallel_corner() {
+    let mut lines = partition_line(&(0., 0.), &(10., 0.), 1.);
+    lines.extend(partition_line(&(10., 0.), &(10., 10.), 1.));
+    let thicknesses = vec![4.; lines.len()];
+    let copies = thicken_lines_parallel(&lines, &thicknesses, 1., false);
+    // four connected copies without spikes, the outer ones meet at mitered corners two spacings off the path
+    let gaps = copies
+        .iter()
+        .zip(copies.iter().skip(1))
+        .filter(|(line, next)| line.1 != next.0)
+        .count();
+    assert_eq!(gaps, 3);
+    let distance = |(x, y): (f64, f64)| {
+        let horizontal = (x.clamp(0., 10.) - x).hypot(y);
+        let vertical = (x - 10.).hypot(y.clamp(0., 10.) - y);
+        horizontal.min(vertical)
+    };
+    for (start, stop) in &copies {
+        assert!(distance(*start) <= 2. * 2f64.sqrt() + 1e-9);
+        assert!(length(start, stop) <= 1.5);
+    }
+}
+
+#[test]
+fn parallel_varying_thickness() {
+    let lines = partition_line(&(0., 0.), &(200., 0.), 1.);
+    let thicknesses: Vec<f64> = (0..lines.len())
+        .map(|i| 3. + 2. * (i as f64 / 10.).sin())
+        .collect();
+    let copies = thicken_lines_parallel(&lines, &thicknesses, 1., false);
+    // copies only start or stop where the number of copies changes, not at every line
+    let polylines = join_lines(&copies, 1e-9);
+    assert!(polylines.len() <= 20, "{} polylines", polylines.len());
+    let path = copies
+        .iter()
+        .filter(|(start, stop)| start.1 == 0. && stop.1 == 0.)
+        .map(|(start, stop)| length(start, stop))
+        .sum::<f64>();
+    assert!((path - 200.).abs() < 1e-9);
+}
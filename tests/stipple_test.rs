@@ -0,0 +1,53 @@
+use image::{ImageBuffer, Luma};
+use line_approximator_lib::stipple::{draw_dots, DotShape, Stipple};
+
+#[test]
+fn stipple_points() {
+    // dark left half, white right half
+    let image: ImageBuffer<Luma<u8>, Vec<u8>> =
+        ImageBuffer::from_fn(40, 20, |x, _| if x < 20 { Luma([0]) } else { Luma([255]) });
+    let points: Vec<(f64, f64)> = Stipple::new(&image, 50, 10, 7).points();
+    assert_eq!(points.len(), 50);
+    assert!(points.iter().all(|point| point.0 < 20. && (0. ..=20.).contains(&point.1)));
+    // relaxed dots keep a distance
+    for (i, point) in points.iter().enumerate() {
+        for other in &points[i + 1..] {
+            assert!((point.0 - other.0).hypot(point.1 - other.1) > 1.);
+        }
+    }
+    // same seed, same dots
+    assert_eq!(points, Stipple::new(&image, 50, 10, 7).points());
+    assert_ne!(points, Stipple::new(&image, 50, 10, 8).points());
+    // the random generator is pinned, so a seed gives the same dots across dependency updates
+    let sampled: Vec<(f64, f64)> = Stipple::new(&image, 2, 0, 7).points();
+    let expected = [
+        (3.167989362772101, 3.7042761280364562),
+        (10.601259015284285, 14.359364368908793),
+    ];
+    for (point, expected) in sampled.iter().zip(expected) {
+        assert!((point.0 - expected.0).abs() < 1e-9 && (point.1 - expected.1).abs() < 1e-9);
+    }
+
+    // images of uniform brightness are stippled by their absolute darkness, white ones get no dots
+    for brightness in [0, 128] {
+        let flat: ImageBuffer<Luma<u8>, Vec<u8>> =
+            ImageBuffer::from_pixel(10, 10, Luma([brightness]));
+        assert_eq!(Stipple::<f64>::new(&flat, 50, 10, 7).points().len(), 50);
+    }
+    let white: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::from_pixel(10, 10, Luma([255]));
+    assert!(Stipple::<f64>::new(&white, 50, 10, 7).points().is_empty());
+}
+
+#[test]
+fn stipple_dots() {
+    let points = [(5f64, 5f64), (10., 10.)];
+    let circles = draw_dots(&points, 2., DotShape::Circle);
+    // sides of at most one pixel
+    assert_eq!(circles.len(), 2 * 13);
+    for (start, stop) in &circles {
+        assert!((start.0 - stop.0).hypot(start.1 - stop.1) <= 1.);
+    }
+    assert_eq!(circles[0].0, circles[12].1);
+    let segments = draw_dots(&points, 2., DotShape::Segment);
+    assert_eq!(segments, vec![((3., 5.), (7., 5.)), ((8., 10.), (12., 10.))]);
+}